@@ -1,5 +1,5 @@
 use backer::{
-  models::{Align, Area},
+  models::{Align, Area, ScrollAxis},
   nodes::*,
   Layout, Node,
 };
@@ -27,6 +27,7 @@ fn main() -> eframe::Result {
 struct MyApp {
   items: Vec<Item>,
   show_backer: bool,
+  scroll: f32,
 }
 
 struct Item {
@@ -56,6 +57,7 @@ impl Default for MyApp {
         })
         .collect(),
       show_backer: true,
+      scroll: 0.,
     }
   }
 }
@@ -80,23 +82,28 @@ struct State<'a> {
   ui: &'a mut Ui,
   bounties: &'a mut Vec<Item>,
   backer_on: &'a mut bool,
+  scroll: &'a mut f32,
 }
 
 impl eframe::App for MyApp {
   fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
     egui::CentralPanel::default().show(ctx, |ui| {
-      let viewport = ctx.input(|i| i.screen_rect());
       if self.show_backer {
-        ScrollArea::vertical().show_viewport(ui, |ui, scroll_rect| {
-          let mut state = State {
-            ui,
-            bounties: &mut self.items,
-            backer_on: &mut self.show_backer,
-          };
-          let mut area = area_from(scroll_rect);
-          area.y = -area.y;
-          area.width = viewport.width();
-          Layout::new(|state: &mut State| {
+        let area = area_from(ui.available_rect_before_wrap());
+        self.scroll -= ctx.input(|i| i.smooth_scroll_delta.y);
+        let mut state = State {
+          ui,
+          bounties: &mut self.items,
+          backer_on: &mut self.show_backer,
+          scroll: &mut self.scroll,
+        };
+        Layout::new(|state: &mut State| {
+          scroll(
+            ScrollAxis::Vertical,
+            |info, state: &mut State| {
+              *state.scroll = state.scroll.clamp(0., info.max_offset_y());
+              (0., *state.scroll)
+            },
             column_spaced(
               10.,
               vec![
@@ -197,10 +204,10 @@ impl eframe::App for MyApp {
             )
             .align_contents(Align::Top)
             .pad(10.)
-            .align(Align::Top)
-          })
-          .draw(area, &mut state);
-        });
+            .align(Align::Top),
+          )
+        })
+        .draw(area, &mut state);
       } else {
         ScrollArea::vertical().show(ui, |ui| {
           ui.vertical_centered_justified(|ui| {
@@ -0,0 +1,181 @@
+use crate::{
+    layout::NodeValue,
+    models::{Area, Easing},
+    traits::{NodeTrait, Scalar},
+};
+use alloc::collections::{BTreeMap, BTreeSet};
+use core::{mem::take, time::Duration};
+
+/// The animations of identified nodes, kept by a [`crate::Layout`] between frames
+#[derive(Debug)]
+pub(crate) struct Animator<S> {
+    pub(crate) time: Duration,
+    animations: BTreeMap<u64, AreaAnimation<S>>,
+    seen: BTreeSet<u64>,
+}
+
+impl<S> Default for Animator<S> {
+    fn default() -> Self {
+        Self {
+            time: Duration::ZERO,
+            animations: BTreeMap::new(),
+            seen: BTreeSet::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct AreaAnimation<S> {
+    from: Area<S>,
+    to: Area<S>,
+    start: Duration,
+    duration: Duration,
+    easing: Easing,
+}
+
+/// How far through an animation that started at `start` is at `time`, from 0 to 1
+pub(crate) fn progress(start: Duration, duration: Duration, time: Duration) -> f32 {
+    if duration.is_zero() {
+        return 1.;
+    }
+    (time.saturating_sub(start).as_secs_f32() / duration.as_secs_f32()).min(1.)
+}
+
+impl<S: Scalar> AreaAnimation<S> {
+    fn progress(&self, time: Duration) -> f32 {
+        progress(self.start, self.duration, time)
+    }
+    fn current(&self, time: Duration) -> Area<S> {
+        let t = S::from_f64(self.easing.ease(self.progress(time)) as f64);
+        self.from.lerp(self.to, t)
+    }
+}
+
+impl<S: Scalar> Animator<S> {
+    /// The area to draw the node with the id `id` in, where `target` is the area it was laid out in this frame
+    fn area(&mut self, id: u64, target: Area<S>, duration: Duration, easing: Easing) -> Area<S> {
+        self.seen.insert(id);
+        let time = self.time;
+        let animation = self.animations.entry(id).or_insert(AreaAnimation {
+            from: target,
+            to: target,
+            start: time,
+            duration,
+            easing,
+        });
+        if animation.to != target {
+            // Animations that are interrupted continue from wherever they were
+            *animation = AreaAnimation {
+                from: animation.current(time),
+                to: target,
+                start: time,
+                duration,
+                easing,
+            };
+        }
+        animation.current(time)
+    }
+    /// Forgets the animations of nodes that weren't in this frame's layout
+    pub(crate) fn finish_frame(&mut self) {
+        let seen = take(&mut self.seen);
+        self.animations.retain(|id, _| seen.contains(id));
+    }
+    pub(crate) fn is_animating(&self) -> bool {
+        self.animations
+            .values()
+            .any(|animation| animation.from != animation.to && animation.progress(self.time) < 1.)
+    }
+}
+
+impl<State, Ctx, S: Scalar> NodeValue<State, Ctx, S> {
+    /// The id of this node, or of the identified node it wraps through modifiers that don't add siblings,
+    /// so `.id("a").pad(5.).animate(..)` is matched between frames by the id `"a"`
+    pub(crate) fn wrapped_id(&self) -> Option<u64> {
+        match self {
+            NodeValue::Identified { id, .. }
+            | NodeValue::Animated { id: Some(id), .. }
+            | NodeValue::Transition { id: Some(id), .. } => Some(*id),
+            NodeValue::Padding { element, .. }
+            | NodeValue::Explicit { element, .. }
+            | NodeValue::Clip { element }
+            | NodeValue::Direction { element, .. }
+            | NodeValue::Animated { element, .. }
+            | NodeValue::Transition { element, .. }
+            | NodeValue::Offset { element, .. }
+            | NodeValue::Coupled { element, .. } => element.kind.wrapped_id(),
+            _ => None,
+        }
+    }
+
+    /// Moves the areas of animated nodes & their children to their current animated areas
+    pub(crate) fn animate(&mut self, animator: &mut Animator<S>) {
+        match self {
+            NodeValue::Animated {
+                id,
+                duration,
+                easing,
+                element,
+            } => {
+                let target = element.occupied_area();
+                // Nested animations are relative to this node's animation
+                element.animate(animator);
+                if let (Some(id), Some(target)) = (*id, target) {
+                    let area = animator.area(id, target, *duration, *easing);
+                    if area != target {
+                        element.map_areas(&|child| transformed(child, target, area));
+                    }
+                }
+            }
+            NodeValue::Scope { scoped } => scoped.animate(animator),
+            _ => self
+                .children_mut()
+                .into_iter()
+                .for_each(|child| child.animate(animator)),
+        }
+    }
+
+    /// Replaces every area computed during layout in this node & its children with `f(area)`
+    pub(crate) fn map_areas(&mut self, f: &dyn Fn(Area<S>) -> Area<S>) {
+        match self {
+            NodeValue::Draw(drawable) => drawable.area = f(drawable.area),
+            NodeValue::Scroll { viewport, .. } => *viewport = f(*viewport),
+            NodeValue::Scope { scoped } => scoped.map_areas(f),
+            _ => (),
+        }
+        self.children_mut()
+            .into_iter()
+            .for_each(|child| child.map_areas(f));
+    }
+
+    /// Multiplies the opacity of every draw node in this node & its children by `opacity`
+    pub(crate) fn fade(&mut self, opacity: f32) {
+        match self {
+            NodeValue::Draw(drawable) => drawable.opacity *= opacity,
+            NodeValue::Scope { scoped } => scoped.fade(opacity),
+            _ => (),
+        }
+        self.children_mut()
+            .into_iter()
+            .for_each(|child| child.fade(opacity));
+    }
+}
+
+/// Moves & scales `area` as if `from` were moved & scaled to `to`
+pub(crate) fn transformed<S: Scalar>(area: Area<S>, from: Area<S>, to: Area<S>) -> Area<S> {
+    let scale_x = if from.width > S::ZERO {
+        to.width / from.width
+    } else {
+        S::ONE
+    };
+    let scale_y = if from.height > S::ZERO {
+        to.height / from.height
+    } else {
+        S::ONE
+    };
+    Area {
+        x: to.x + (area.x - from.x) * scale_x,
+        y: to.y + (area.y - from.y) * scale_y,
+        width: area.width * scale_x,
+        height: area.height * scale_y,
+    }
+}
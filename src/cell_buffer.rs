@@ -0,0 +1,137 @@
+use crate::prelude::*;
+use crate::{
+    models::{Area, CellRect, Monospace},
+    traits::TextMeasurer,
+};
+use core::fmt;
+
+/// An in-memory grid of character cells, for drawing terminal layouts without a terminal (see [`crate::Layout::cells`])
+///
+/// Text is measured as one cell per character, so a `CellBuffer` can be used as the `Ctx` of [`crate::nodes::text`] nodes.
+///
+/// ```rust
+/// use backer::*;
+/// use backer::models::*;
+/// use backer::nodes::*;
+///
+/// let mut buffer = CellBuffer::new(12, 3);
+/// Layout::new_with(|_: &mut (), _: &mut CellBuffer| {
+///     row(vec![
+///         text("Hi", (), |area, _, buffer: &mut CellBuffer| {
+///             buffer.print(area.to_cells(), "Hi")
+///         })
+///         .pad(1.),
+///         draw_with(|area, _, buffer: &mut CellBuffer| buffer.border(area.to_cells())),
+///     ])
+/// })
+/// .cells()
+/// .draw_with(buffer.area(), &mut (), &mut buffer);
+/// assert_eq!(buffer.to_string(), [
+///     "    ┌──────┐",
+///     " Hi │      │",
+///     "    └──────┘",
+/// ].join("\n"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellBuffer {
+    width: u32,
+    height: u32,
+    cells: Vec<char>,
+}
+
+impl CellBuffer {
+    /// Creates a buffer of `width` columns & `height` rows, filled with spaces
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![' '; width as usize * height as usize],
+        }
+    }
+    /// The number of columns in the buffer
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+    /// The number of rows in the buffer
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+    /// The whole buffer, as an area to lay out in
+    pub fn area(&self) -> Area {
+        Area::from(CellRect::new(0, 0, self.width, self.height))
+    }
+    /// The character at column `x` & row `y`, or `None` outside the buffer
+    pub fn get(&self, x: i32, y: i32) -> Option<char> {
+        self.index(x, y).map(|index| self.cells[index])
+    }
+    /// Sets the character at column `x` & row `y`, doing nothing outside the buffer
+    pub fn set(&mut self, x: i32, y: i32, character: char) {
+        if let Some(index) = self.index(x, y) {
+            self.cells[index] = character;
+        }
+    }
+    /// Sets every cell in `rect` to `character`
+    pub fn fill(&mut self, rect: CellRect, character: char) {
+        for y in rect.y..rect.y + rect.height as i32 {
+            for x in rect.x..rect.x + rect.width as i32 {
+                self.set(x, y, character);
+            }
+        }
+    }
+    /// Writes `text` into `rect`, wrapped the same way it's measured & cut off at the edges of `rect`
+    pub fn print(&mut self, rect: CellRect, text: &str) {
+        let lines = Monospace::default().wrap(text, Some(rect.width as f32));
+        for (row, line) in lines.iter().take(rect.height as usize).enumerate() {
+            for (column, character) in line.chars().take(rect.width as usize).enumerate() {
+                self.set(rect.x + column as i32, rect.y + row as i32, character);
+            }
+        }
+    }
+    /// Draws a box around the edge of `rect` with line drawing characters
+    pub fn border(&mut self, rect: CellRect) {
+        if rect.width == 0 || rect.height == 0 {
+            return;
+        }
+        let (left, top) = (rect.x, rect.y);
+        let right = rect.x + rect.width as i32 - 1;
+        let bottom = rect.y + rect.height as i32 - 1;
+        for x in left..=right {
+            self.set(x, top, '─');
+            self.set(x, bottom, '─');
+        }
+        for y in top..=bottom {
+            self.set(left, y, '│');
+            self.set(right, y, '│');
+        }
+        self.set(left, top, '┌');
+        self.set(right, top, '┐');
+        self.set(left, bottom, '└');
+        self.set(right, bottom, '┘');
+    }
+    /// Each row of the buffer, without trailing spaces
+    pub fn lines(&self) -> Vec<String> {
+        self.cells
+            .chunks(self.width.max(1) as usize)
+            .take(self.height as usize)
+            .map(|row| row.iter().collect::<String>().trim_end().to_string())
+            .collect()
+    }
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        (x >= 0 && y >= 0 && (x as u32) < self.width && (y as u32) < self.height)
+            .then(|| y as usize * self.width as usize + x as usize)
+    }
+}
+
+impl fmt::Display for CellBuffer {
+    /// Writes the rows of the buffer on separate lines, without trailing spaces
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.lines().join("\n"))
+    }
+}
+
+impl TextMeasurer for CellBuffer {
+    type Style = ();
+    fn measure_text(&mut self, text: &str, style: &(), max_width: Option<f32>) -> (f32, f32) {
+        Monospace::default().measure_text(text, style, max_width)
+    }
+}
@@ -0,0 +1,235 @@
+use crate::prelude::*;
+use crate::{
+    layout::NodeValue,
+    models::{Area, ComputedNode, NodeKind},
+    node_cache::NodeCache,
+    traits::{NodeTrait, Scalar},
+};
+use alloc::collections::BTreeSet;
+use core::hash::{Hash, Hasher};
+
+pub(crate) fn hash_id<Id: Hash + ?Sized>(id: &Id) -> u64 {
+    let mut hasher = StableHasher::default();
+    id.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// An FNV-1a hasher, which unlike `std`'s `DefaultHasher` gives the same hashes on every platform & Rust version,
+/// so exported ids only change when the layout does (see [`crate::ComputedLayout::to_json`])
+///
+/// Integers are hashed as 64 bit values, so ids compare by value whatever their integer type.
+pub(crate) struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        StableHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    fn finish(&self) -> u64 {
+        self.0
+    }
+    fn write_u8(&mut self, i: u8) {
+        self.write_u64(i as u64)
+    }
+    fn write_u16(&mut self, i: u16) {
+        self.write_u64(i as u64)
+    }
+    fn write_u32(&mut self, i: u32) {
+        self.write_u64(i as u64)
+    }
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes())
+    }
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64)
+    }
+    fn write_i8(&mut self, i: i8) {
+        self.write_i64(i as i64)
+    }
+    fn write_i16(&mut self, i: i16) {
+        self.write_i64(i as i64)
+    }
+    fn write_i32(&mut self, i: i32) {
+        self.write_i64(i as i64)
+    }
+    fn write_i64(&mut self, i: i64) {
+        self.write_u64(i as u64)
+    }
+    fn write_isize(&mut self, i: isize) {
+        self.write_i64(i as i64)
+    }
+}
+
+impl<State, Ctx, S: Scalar> NodeValue<State, Ctx, S> {
+    pub(crate) fn kind(&self) -> NodeKind {
+        match self {
+            NodeValue::Padding { .. } => NodeKind::Padding,
+            NodeValue::Column { .. } => NodeKind::Column,
+            NodeValue::Row { .. } => NodeKind::Row,
+            NodeValue::Stack { .. } => NodeKind::Stack,
+            NodeValue::Wrap { .. } => NodeKind::Wrap,
+            NodeValue::Grid { .. } => NodeKind::Grid,
+            NodeValue::Group(_) => NodeKind::Group,
+            NodeValue::Identified { .. } => NodeKind::Identified,
+            NodeValue::Clip { .. } => NodeKind::Clip,
+            NodeValue::Direction { .. } => NodeKind::Direction,
+            NodeValue::Animated { .. } => NodeKind::Animated,
+            NodeValue::Transition { .. } => NodeKind::Transition,
+            NodeValue::Offset { .. } => NodeKind::Offset,
+            NodeValue::Draw(_) => NodeKind::Draw,
+            NodeValue::Explicit { .. } => NodeKind::Explicit,
+            NodeValue::Empty => NodeKind::Empty,
+            NodeValue::Space => NodeKind::Space,
+            NodeValue::Scope { .. } => NodeKind::Scope,
+            NodeValue::AreaReader { .. } => NodeKind::AreaReader,
+            NodeValue::Coupled { over, .. } => NodeKind::Coupled { over: *over },
+            NodeValue::Scroll { .. } => NodeKind::Scroll,
+            NodeValue::Virtual { .. } => NodeKind::Virtual,
+        }
+    }
+
+    /// Creates a snapshot of this node & its children after layout, where `area` is the area this node was laid out in
+    pub(crate) fn computed(&self, area: Area<S>) -> ComputedNode<S> {
+        let children: Vec<ComputedNode<S>> = match self {
+            NodeValue::Padding { element, .. }
+            | NodeValue::Explicit { element, .. }
+            | NodeValue::Identified { element, .. }
+            | NodeValue::Clip { element }
+            | NodeValue::Direction { element, .. }
+            | NodeValue::Animated { element, .. }
+            | NodeValue::Transition { element, .. }
+            | NodeValue::Offset { element, .. }
+            | NodeValue::Scroll { element, .. } => element.computed(area),
+            NodeValue::Column { elements, .. }
+            | NodeValue::Row { elements, .. }
+            | NodeValue::Stack { elements, .. }
+            | NodeValue::Wrap { elements, .. }
+            | NodeValue::Grid { elements, .. }
+            | NodeValue::Virtual { elements, .. }
+            | NodeValue::Group(elements) => elements
+                .iter()
+                .flat_map(|element| element.computed(area))
+                .collect(),
+            NodeValue::Coupled {
+                element, coupled, ..
+            } => element
+                .computed(area)
+                .into_iter()
+                .chain(coupled.computed(area))
+                .collect(),
+            NodeValue::Scope { scoped } => scoped.computed(area),
+            NodeValue::Draw(_)
+            | NodeValue::Empty
+            | NodeValue::Space
+            | NodeValue::AreaReader { .. } => Vec::new(),
+        };
+        ComputedNode {
+            kind: self.kind(),
+            id: if let NodeValue::Identified { id, .. } = self {
+                Some(*id)
+            } else {
+                None
+            },
+            // These nodes only position their child, so they occupy the same area
+            area: match self {
+                NodeValue::Explicit { .. }
+                | NodeValue::Identified { .. }
+                | NodeValue::Clip { .. }
+                | NodeValue::Direction { .. }
+                | NodeValue::Animated { .. }
+                | NodeValue::Transition { .. }
+                | NodeValue::Offset { .. } => {
+                    children.first().map(|child| child.area).unwrap_or(area)
+                }
+                _ => area,
+            },
+            children,
+        }
+    }
+}
+
+impl<State, Ctx, S: Scalar> NodeCache<State, Ctx, S> {
+    /// Returns an id that is used more than once within the same scope, adding the ids it finds to `seen`
+    pub(crate) fn duplicate_id(&self, seen: &mut BTreeSet<u64>) -> Option<u64> {
+        // Nodes that weren't laid out can't be found, so their ids can't conflict
+        self.layout_area?;
+        self.kind.duplicate_id(seen)
+    }
+}
+
+impl<State, Ctx, S: Scalar> NodeValue<State, Ctx, S> {
+    pub(crate) fn duplicate_id(&self, seen: &mut BTreeSet<u64>) -> Option<u64> {
+        if let NodeValue::Identified { id, .. } = self {
+            if !seen.insert(*id) {
+                return Some(*id);
+            }
+        }
+        if let NodeValue::Scope { scoped } = self {
+            // Scopes have their own set of ids
+            if let Some(id) = scoped.duplicate_id() {
+                return Some(id);
+            }
+        }
+        self.children()
+            .into_iter()
+            .find_map(|child| child.duplicate_id(seen))
+    }
+}
+
+impl<S: Scalar> ComputedNode<S> {
+    /// Finds the first node with the id `id` in this node & its descendants, in the order they were defined
+    pub fn find(&self, id: impl Hash) -> Option<&ComputedNode<S>> {
+        self.find_hashed(hash_id(&id))
+    }
+    pub(crate) fn find_hashed(&self, id: u64) -> Option<&ComputedNode<S>> {
+        if self.id == Some(id) {
+            return Some(self);
+        }
+        self.children.iter().find_map(|child| child.find_hashed(id))
+    }
+    /// Whether this node was identified with `id` (see [`crate::NodeWith::id`])
+    pub fn has_id(&self, id: impl Hash) -> bool {
+        self.id == Some(hash_id(&id))
+    }
+    /// The node's children, in the order they are drawn
+    pub fn children_in_paint_order(&self) -> Vec<&ComputedNode<S>> {
+        match self.kind {
+            NodeKind::Row | NodeKind::Column => self.children.iter().rev().collect(),
+            NodeKind::Coupled { over: false } => self.children.iter().rev().collect(),
+            _ => self.children.iter().collect(),
+        }
+    }
+    /// Finds every identified node containing the point at `x`, `y`,
+    /// ordered from the last drawn (top-most) node to the first drawn node.
+    pub fn hit_test(&self, x: S, y: S) -> Vec<&ComputedNode<S>> {
+        fn visit<'a, S: Scalar>(
+            node: &'a ComputedNode<S>,
+            x: S,
+            y: S,
+            hits: &mut Vec<&'a ComputedNode<S>>,
+        ) {
+            if node.id.is_some() && node.area.contains(x, y) {
+                hits.push(node);
+            }
+            // Content that's clipped or scrolled out of view can't be hit
+            if matches!(node.kind, NodeKind::Scroll | NodeKind::Clip) && !node.area.contains(x, y) {
+                return;
+            }
+            node.children_in_paint_order()
+                .into_iter()
+                .for_each(|child| visit(child, x, y, hits));
+        }
+        let mut hits = Vec::new();
+        visit(self, x, y, &mut hits);
+        hits.reverse();
+        hits
+    }
+}
@@ -1,21 +1,27 @@
 use crate::{
-    layout::NodeValue,
-    models::{Area, Size, XAlign, YAlign},
-    traits::NodeTrait,
+    layout::{grid_tracks, wrap_lines, NodeValue, Orientation},
+    models::{Area, ProposedSize, Size, XAlign, YAlign},
+    traits::{NodeTrait, Scalar},
 };
 
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub(crate) struct SizeConstraints {
-    pub(crate) width: Constraint,
-    pub(crate) height: Constraint,
-    pub(crate) aspect: Option<f32>,
+pub(crate) struct SizeConstraints<S = f32> {
+    pub(crate) width: Constraint<S>,
+    pub(crate) height: Constraint<S>,
+    pub(crate) aspect: Option<S>,
     pub(crate) expand_x: bool,
     pub(crate) expand_y: bool,
     pub(crate) x_align: Option<XAlign>,
     pub(crate) y_align: Option<YAlign>,
+    pub(crate) column_span: Option<usize>,
+    pub(crate) row_span: Option<usize>,
+    pub(crate) weight: Option<S>,
+    /// The bounds set on the node that conflicted, before they were resolved (see [`Constraint::new`])
+    pub(crate) width_conflict: Option<(S, S)>,
+    pub(crate) height_conflict: Option<(S, S)>,
 }
 
-impl Default for SizeConstraints {
+impl<S: Scalar> Default for SizeConstraints<S> {
     fn default() -> Self {
         SizeConstraints {
             width: Constraint::none(),
@@ -25,36 +31,54 @@ impl Default for SizeConstraints {
             expand_y: false,
             x_align: None,
             y_align: None,
+            column_span: None,
+            row_span: None,
+            weight: None,
+            width_conflict: None,
+            height_conflict: None,
         }
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub(crate) struct Constraint {
-    lower: Option<f32>,
-    upper: Option<f32>,
+pub(crate) struct Constraint<S = f32> {
+    lower: Option<S>,
+    upper: Option<S>,
 }
 
-impl Constraint {
-    pub(crate) fn new(lower: Option<f32>, upper: Option<f32>) -> Self {
-        assert!(Self::check_constraints(lower, upper));
-        Self { lower, upper }
+impl<S: Scalar> Constraint<S> {
+    /// Creates a constraint between `lower` & `upper`.
+    ///
+    /// Bounds that aren't numbers are ignored, & if the lower bound is greater than the upper bound
+    /// the lower bound is used for both, rather than panicking mid-layout (see [`crate::models::ConstraintConflict`]).
+    pub(crate) fn new(lower: Option<S>, upper: Option<S>) -> Self {
+        let lower = lower.filter(|lower| !lower.is_nan());
+        let upper = upper.filter(|upper| !upper.is_nan());
+        Self {
+            lower,
+            upper: match (lower, upper) {
+                (Some(lower), Some(upper)) if lower > upper => Some(lower),
+                _ => upper,
+            },
+        }
     }
-    pub(crate) fn get_lower(&self) -> Option<f32> {
+    pub(crate) fn get_lower(&self) -> Option<S> {
         self.lower
     }
-    pub(crate) fn set_lower(&mut self, value: Option<f32>) {
-        assert!(Self::check_constraints(value, self.upper));
-        self.lower = value;
+    pub(crate) fn set_lower(&mut self, value: Option<S>) {
+        *self = Self::new(value, self.upper);
     }
-    pub(crate) fn get_upper(&self) -> Option<f32> {
+    pub(crate) fn get_upper(&self) -> Option<S> {
         self.upper
     }
-    pub(crate) fn set_upper(&mut self, value: Option<f32>) {
-        assert!(Self::check_constraints(self.lower, value));
-        self.upper = value;
+    pub(crate) fn set_upper(&mut self, value: Option<S>) {
+        *self = Self::new(self.lower, value);
+    }
+    /// The bounds, if the lower bound is greater than the upper bound
+    fn conflict(lower: Option<S>, upper: Option<S>) -> Option<(S, S)> {
+        lower.zip(upper).filter(|(lower, upper)| lower > upper)
     }
-    pub(crate) fn clamp(&self, value: f32) -> f32 {
+    pub(crate) fn clamp(&self, value: S) -> S {
         match (self.lower, self.upper) {
             (None, None) => value,
             (None, Some(upper)) => value.min(upper),
@@ -62,33 +86,28 @@ impl Constraint {
             (Some(lower), Some(upper)) => value.clamp(lower, upper),
         }
     }
-    fn check_constraints(lower: Option<f32>, upper: Option<f32>) -> bool {
-        if let (Some(lower_unwrapped), Some(upper_unwrapped)) = (lower, upper) {
-            lower_unwrapped <= upper_unwrapped
-        } else {
-            true
-        }
-    }
 }
 
-impl<State, Ctx> NodeValue<State, Ctx> {
+impl<State, Ctx, S: Scalar> NodeValue<State, Ctx, S> {
     pub(crate) fn constraints(
         &mut self,
-        available_area: Area,
+        available_area: Area<S>,
+        cells: bool,
         state: &mut State,
         ctx: &mut Ctx,
-    ) -> SizeConstraints {
+    ) -> SizeConstraints<S> {
         let contextual_aligns = self.contextual_aligns();
         let allocations = self.allocate_area(
             available_area,
             contextual_aligns.0,
             contextual_aligns.1,
+            cells,
             state,
             ctx,
         );
         match self {
             NodeValue::Padding { amounts, element } => {
-                let child = element.constraints(allocations[0], state, ctx);
+                let child = element.constraints(allocations[0], cells, state, ctx);
                 SizeConstraints {
                     width: Constraint::new(
                         child
@@ -110,6 +129,10 @@ impl<State, Ctx> NodeValue<State, Ctx> {
                             .get_upper()
                             .map(|upper| upper + amounts.top + amounts.bottom),
                     ),
+                    // Spans & weights apply to the space the padded node is placed in
+                    column_span: child.column_span,
+                    row_span: child.row_span,
+                    weight: child.weight,
                     ..Default::default()
                 }
             }
@@ -121,21 +144,21 @@ impl<State, Ctx> NodeValue<State, Ctx> {
                 .iter_mut()
                 .zip(allocations.iter())
                 .fold(
-                    Option::<SizeConstraints>::None,
+                    Option::<SizeConstraints<S>>::None,
                     |current, (element, allocated)| {
                         if let Some(current) = current {
                             Some(SizeConstraints {
                                 width: current.width.combine_adjacent_priority(
-                                    element.constraints(*allocated, state, ctx).width,
+                                    element.constraints(*allocated, cells, state, ctx).width,
                                 ),
                                 height: current.height.combine_sum(
-                                    element.constraints(*allocated, state, ctx).height,
+                                    element.constraints(*allocated, cells, state, ctx).height,
                                     *spacing,
                                 ),
                                 ..Default::default()
                             })
                         } else {
-                            Some(element.constraints(*allocated, state, ctx))
+                            Some(element.constraints(*allocated, cells, state, ctx))
                         }
                     },
                 )
@@ -148,61 +171,178 @@ impl<State, Ctx> NodeValue<State, Ctx> {
                 .iter_mut()
                 .zip(allocations.iter())
                 .fold(
-                    Option::<SizeConstraints>::None,
+                    Option::<SizeConstraints<S>>::None,
                     |current, (element, allocated)| {
                         if let Some(current) = current {
                             Some(SizeConstraints {
                                 width: current.width.combine_sum(
-                                    element.constraints(*allocated, state, ctx).width,
+                                    element.constraints(*allocated, cells, state, ctx).width,
                                     *spacing,
                                 ),
                                 height: current.height.combine_adjacent_priority(
-                                    element.constraints(*allocated, state, ctx).height,
+                                    element.constraints(*allocated, cells, state, ctx).height,
                                 ),
                                 ..Default::default()
                             })
                         } else {
-                            Some(element.constraints(*allocated, state, ctx))
+                            Some(element.constraints(*allocated, cells, state, ctx))
                         }
                     },
                 )
                 .unwrap_or_default(),
-            NodeValue::Stack { elements, .. } => elements
+            NodeValue::Stack { elements, .. } | NodeValue::Group(elements) => elements
                 .iter_mut()
-                .fold(Option::<SizeConstraints>::None, |current, element| {
+                .fold(Option::<SizeConstraints<S>>::None, |current, element| {
                     if let Some(current) = current {
                         Some(current.combine_adjacent_priority(element.constraints(
                             allocations[0],
+                            cells,
                             state,
                             ctx,
                         )))
                     } else {
-                        Some(element.constraints(allocations[0], state, ctx))
+                        Some(element.constraints(allocations[0], cells, state, ctx))
                     }
                 })
                 .unwrap_or_default(),
+            NodeValue::Wrap {
+                elements,
+                orientation,
+                spacing,
+                line_spacing,
+                ..
+            } => {
+                let wrapped = wrap_lines(
+                    elements,
+                    *orientation,
+                    *spacing,
+                    available_area,
+                    cells,
+                    state,
+                    ctx,
+                );
+                // Wrapping lets the sequence shrink down to its largest element,
+                // or grow to fit every element on a single line
+                let single_line = wrapped
+                    .sizes
+                    .iter()
+                    .map(|size| orientation.main(size))
+                    .reduce(|current, next| current.combine_sum(next, *spacing))
+                    .unwrap_or(Constraint::none());
+                let main = Constraint::new(
+                    wrapped
+                        .sizes
+                        .iter()
+                        .filter_map(|size| orientation.main(size).get_lower())
+                        .reduce(S::max),
+                    single_line.get_upper(),
+                );
+                let cross = wrapped
+                    .line_constraints
+                    .into_iter()
+                    .reduce(|current, next| current.combine_sum(next, *line_spacing))
+                    .unwrap_or(Constraint::none());
+                match orientation {
+                    Orientation::Horizontal => SizeConstraints {
+                        width: main,
+                        height: cross,
+                        ..Default::default()
+                    },
+                    Orientation::Vertical => SizeConstraints {
+                        width: cross,
+                        height: main,
+                        ..Default::default()
+                    },
+                }
+            }
+            NodeValue::Grid {
+                elements,
+                columns,
+                rows,
+                column_spacing,
+                row_spacing,
+                ..
+            } => {
+                let tracks =
+                    grid_tracks(elements, columns, rows, available_area, cells, state, ctx);
+                SizeConstraints {
+                    width: tracks
+                        .columns
+                        .iter()
+                        .map(|(constraint, _)| *constraint)
+                        .reduce(|current, next| current.combine_sum(next, *column_spacing))
+                        .unwrap_or(Constraint::none()),
+                    height: tracks
+                        .rows
+                        .iter()
+                        .map(|(constraint, _)| *constraint)
+                        .reduce(|current, next| current.combine_sum(next, *row_spacing))
+                        .unwrap_or(Constraint::none()),
+                    ..Default::default()
+                }
+            }
             NodeValue::Explicit { options, element } => {
                 SizeConstraints::from_size(options.clone(), allocations[0], state, ctx)
-                    .combine_explicit_with_child(element.constraints(allocations[0], state, ctx))
+                    .combine_explicit_with_child(element.constraints(
+                        allocations[0],
+                        cells,
+                        state,
+                        ctx,
+                    ))
             }
-            NodeValue::Offset { element, .. } => element.constraints(allocations[0], state, ctx),
-            NodeValue::Scope { scoped } => scoped.constraints(allocations[0], state, ctx),
-            NodeValue::Draw(_) | NodeValue::Space | NodeValue::AreaReader { .. } => {
-                SizeConstraints::default()
+            NodeValue::Offset { element, .. }
+            | NodeValue::Identified { element, .. }
+            | NodeValue::Clip { element }
+            | NodeValue::Direction { element, .. }
+            | NodeValue::Animated { element, .. }
+            | NodeValue::Transition { element, .. } => {
+                element.constraints(allocations[0], cells, state, ctx)
+            }
+            NodeValue::Scope { scoped } => scoped.constraints(allocations[0], cells, state, ctx),
+            NodeValue::Virtual {
+                orientation,
+                count,
+                extent,
+                extents,
+                ..
+            } => {
+                let total = match extents {
+                    Some(extents) => (0..*count)
+                        .map(|i| extents(i, state, ctx).max(S::ZERO))
+                        .fold(S::ZERO, |total, extent| total + extent),
+                    None => S::from_f64(*count as f64) * *extent,
+                };
+                let main = Constraint::new(Some(total), Some(total));
+                match orientation {
+                    Orientation::Horizontal => SizeConstraints {
+                        width: main,
+                        ..Default::default()
+                    },
+                    Orientation::Vertical => SizeConstraints {
+                        height: main,
+                        ..Default::default()
+                    },
+                }
+            }
+            NodeValue::Draw(_)
+            | NodeValue::Space
+            | NodeValue::Empty
+            | NodeValue::AreaReader { .. }
+            | NodeValue::Scroll { .. } => SizeConstraints::default(),
+            NodeValue::Coupled { element, .. } => {
+                element.constraints(allocations[0], cells, state, ctx)
             }
-            NodeValue::Coupled { element, .. } => element.constraints(allocations[0], state, ctx),
-            NodeValue::Empty | NodeValue::Group(_) => unreachable!(),
         }
     }
 }
 
-impl Constraint {
+impl<S: Scalar> Constraint<S> {
     pub(crate) fn none() -> Self {
         Self::new(None, None)
     }
 }
 
-impl SizeConstraints {
+impl<S: Scalar> SizeConstraints<S> {
     pub(crate) fn combine_adjacent_priority(self, other: Self) -> Self {
         SizeConstraints {
             width: self.width.combine_adjacent_priority(other.width),
@@ -237,12 +377,17 @@ impl SizeConstraints {
             expand_y: self.expand_y,
             x_align: self.x_align,
             y_align: self.y_align,
+            column_span: self.column_span.or(child.column_span),
+            row_span: self.row_span.or(child.row_span),
+            weight: self.weight.or(child.weight),
+            width_conflict: self.width_conflict,
+            height_conflict: self.height_conflict,
         }
     }
 }
 
-impl Constraint {
-    pub(crate) fn clamping(&self, value: f32) -> f32 {
+impl<S: Scalar> Constraint<S> {
+    pub(crate) fn clamping(&self, value: S) -> S {
         match (self.get_lower(), self.get_upper()) {
             (None, None) => value,
             (None, Some(upper)) => value.min(upper),
@@ -252,7 +397,7 @@ impl Constraint {
     }
 }
 
-impl Constraint {
+impl<S: Scalar> Constraint<S> {
     pub(crate) fn combine_adjacent_priority(self, other: Self) -> Self {
         // This always takes the bigger bound
         let lower = match (self.get_lower(), other.get_lower()) {
@@ -284,7 +429,7 @@ impl Constraint {
                 .or(child.upper.map(|cl| cl.max(self.lower.unwrap_or(cl)))),
         )
     }
-    pub(crate) fn combine_sum(self, other: Self, spacing: f32) -> Self {
+    pub(crate) fn combine_sum(self, other: Self, spacing: S) -> Self {
         let lower = match (self.get_lower(), other.get_lower()) {
             (None, None) => None,
             (None, Some(bound)) | (Some(bound), None) => Some(bound + spacing),
@@ -299,8 +444,13 @@ impl Constraint {
     }
 }
 
-impl SizeConstraints {
-    pub(crate) fn from_size<A, B>(value: Size<A, B>, area: Area, a: &mut A, b: &mut B) -> Self {
+impl<S: Scalar> SizeConstraints<S> {
+    pub(crate) fn from_size<A, B>(
+        value: Size<A, B, S>,
+        area: Area<S>,
+        a: &mut A,
+        b: &mut B,
+    ) -> Self {
         let mut initial = SizeConstraints {
             width: if value.width_min.is_some() || value.width_max.is_some() {
                 Constraint::new(value.width_min, value.width_max)
@@ -317,6 +467,11 @@ impl SizeConstraints {
             expand_y: value.expand_y,
             x_align: value.x_align,
             y_align: value.y_align,
+            column_span: value.column_span,
+            row_span: value.row_span,
+            weight: value.weight,
+            width_conflict: Constraint::conflict(value.width_min, value.width_max),
+            height_conflict: Constraint::conflict(value.height_min, value.height_max),
         };
         if let Some(dynamic) = value.dynamic_height {
             let result = Some(initial.height.clamp(dynamic(area.width, a, b)));
@@ -328,6 +483,33 @@ impl SizeConstraints {
             initial.width.set_lower(result);
             initial.width.set_upper(result);
         }
+        if let Some(measure) = value.measure {
+            // The node can be anywhere between its min-content & max-content width,
+            // & is as tall as it needs to be to fit the available width
+            let min_content = measure(
+                ProposedSize {
+                    width: Some(S::ZERO),
+                    height: None,
+                },
+                a,
+                b,
+            );
+            let max_content = measure(ProposedSize::default(), a, b);
+            initial.width = Constraint::new(
+                Some(initial.width.clamp(min_content.0)),
+                Some(initial.width.clamp(max_content.0.max(min_content.0))),
+            );
+            let fit = measure(
+                ProposedSize {
+                    width: Some(initial.width.clamp(area.width)),
+                    height: None,
+                },
+                a,
+                b,
+            );
+            let height = Some(initial.height.clamp(fit.1));
+            initial.height = Constraint::new(height, height);
+        }
         initial
     }
 }
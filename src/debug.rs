@@ -1,7 +1,196 @@
-use crate::layout::NodeValue;
-use std::fmt;
+use crate::prelude::*;
+use crate::{
+    constraints::SizeConstraints,
+    layout::NodeValue,
+    models::{Area, ConstraintConflict, DebugNode, Dimension, NodeConstraints},
+    node_cache::NodeCache,
+    traits::Scalar,
+};
+use core::fmt;
 
-impl<State, Ctx> fmt::Debug for NodeValue<State, Ctx> {
+impl<S: Scalar> From<SizeConstraints<S>> for NodeConstraints<S> {
+    fn from(constraints: SizeConstraints<S>) -> Self {
+        NodeConstraints {
+            width_min: constraints.width.get_lower(),
+            width_max: constraints.width.get_upper(),
+            height_min: constraints.height.get_lower(),
+            height_max: constraints.height.get_upper(),
+            aspect: constraints.aspect,
+            expand_x: constraints.expand_x,
+            expand_y: constraints.expand_y,
+        }
+    }
+}
+
+impl<State, Ctx, S: Scalar> NodeCache<State, Ctx, S> {
+    /// Collects the layout of this node & its descendants, parents before children
+    pub(crate) fn collect_debug(&self, depth: usize, nodes: &mut Vec<DebugNode<S>>) {
+        // Nodes that weren't laid out, like virtual items outside of the visible area, are skipped
+        let Some(area) = self.layout_area else {
+            return;
+        };
+        let children = self.kind.children();
+        let padding = match &self.kind {
+            NodeValue::Padding { element, .. } => element
+                .layout_area
+                .map(|inner| padding_gaps(area, inner))
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        };
+        let spacing = match &self.kind {
+            NodeValue::Row { spacing, .. } | NodeValue::Column { spacing, .. }
+                if *spacing > S::ZERO =>
+            {
+                let horizontal = matches!(self.kind, NodeValue::Row { .. });
+                children
+                    .windows(2)
+                    .filter_map(|pair| pair[0].layout_area.zip(pair[1].layout_area))
+                    .map(|(a, b)| spacing_gap(area, a, b, horizontal))
+                    .collect()
+            }
+            _ => Vec::new(),
+        };
+        nodes.push(DebugNode {
+            kind: self.kind.kind(),
+            id: if let NodeValue::Identified { id, .. } = &self.kind {
+                Some(*id)
+            } else {
+                None
+            },
+            depth,
+            area,
+            constraints: self
+                .cached_constraints()
+                .map(|(_, constraints)| constraints.into())
+                .unwrap_or_default(),
+            padding,
+            spacing,
+        });
+        self.kind.debug_children(depth + 1, nodes);
+    }
+}
+
+impl<State, Ctx, S: Scalar> NodeCache<State, Ctx, S> {
+    /// Collects the constraint conflicts of this node & its descendants, where `path` is the path to this node
+    pub(crate) fn collect_conflicts(
+        &self,
+        path: &mut Vec<usize>,
+        id: Option<u64>,
+        conflicts: &mut Vec<ConstraintConflict<S>>,
+    ) {
+        let id = match &self.kind {
+            NodeValue::Identified { id, .. } => Some(*id),
+            _ => id,
+        };
+        // Conflicts are passed up through wrapping nodes, so they're only reported where they were set
+        if let (NodeValue::Explicit { .. }, Some((_, constraints))) =
+            (&self.kind, self.cached_constraints())
+        {
+            [
+                (Dimension::Width, constraints.width_conflict),
+                (Dimension::Height, constraints.height_conflict),
+            ]
+            .into_iter()
+            .filter_map(|(dimension, conflict)| conflict.map(|conflict| (dimension, conflict)))
+            .for_each(|(dimension, (min, max))| {
+                conflicts.push(ConstraintConflict {
+                    path: path.clone(),
+                    id,
+                    dimension,
+                    min,
+                    max,
+                    resolved: min,
+                })
+            });
+        }
+        self.kind.conflict_children(path, id, conflicts);
+    }
+}
+
+impl<State, Ctx, S: Scalar> NodeValue<State, Ctx, S> {
+    pub(crate) fn conflict_children(
+        &self,
+        path: &mut Vec<usize>,
+        id: Option<u64>,
+        conflicts: &mut Vec<ConstraintConflict<S>>,
+    ) {
+        if let NodeValue::Scope { scoped } = self {
+            scoped.conflicts(path, id, conflicts);
+        }
+        for (i, child) in self.children().into_iter().enumerate() {
+            path.push(i);
+            child.collect_conflicts(path, id, conflicts);
+            path.pop();
+        }
+    }
+    pub(crate) fn debug_children(&self, depth: usize, nodes: &mut Vec<DebugNode<S>>) {
+        if let NodeValue::Scope { scoped } = self {
+            scoped.debug_nodes(depth, nodes);
+        }
+        self.children()
+            .into_iter()
+            .for_each(|child| child.collect_debug(depth, nodes));
+    }
+}
+
+/// The strips between `outer` & `inner` on each side, skipping empty ones
+fn padding_gaps<S: Scalar>(outer: Area<S>, inner: Area<S>) -> Vec<Area<S>> {
+    let inner = outer.intersection(inner);
+    [
+        Area {
+            x: outer.x,
+            y: outer.y,
+            width: inner.x - outer.x,
+            height: outer.height,
+        },
+        Area {
+            x: inner.x + inner.width,
+            y: outer.y,
+            width: (outer.x + outer.width) - (inner.x + inner.width),
+            height: outer.height,
+        },
+        Area {
+            x: inner.x,
+            y: outer.y,
+            width: inner.width,
+            height: inner.y - outer.y,
+        },
+        Area {
+            x: inner.x,
+            y: inner.y + inner.height,
+            width: inner.width,
+            height: (outer.y + outer.height) - (inner.y + inner.height),
+        },
+    ]
+    .into_iter()
+    .filter(|gap| gap.width > S::ZERO && gap.height > S::ZERO)
+    .collect()
+}
+
+/// The space between two adjacent children of a sequence
+fn spacing_gap<S: Scalar>(sequence: Area<S>, a: Area<S>, b: Area<S>, horizontal: bool) -> Area<S> {
+    if horizontal {
+        // Children are in reverse order in right to left layouts
+        let (leading, trailing) = if a.x <= b.x { (a, b) } else { (b, a) };
+        let x = leading.x + leading.width;
+        Area {
+            x,
+            y: sequence.y,
+            width: (trailing.x - x).max(S::ZERO),
+            height: sequence.height,
+        }
+    } else {
+        let y = a.y + a.height;
+        Area {
+            x: sequence.x,
+            y,
+            width: sequence.width,
+            height: (b.y - y).max(S::ZERO),
+        }
+    }
+}
+
+impl<State, Ctx, S: Scalar> fmt::Debug for NodeValue<State, Ctx, S> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             NodeValue::Padding { amounts, element } => f
@@ -14,24 +203,28 @@ impl<State, Ctx> fmt::Debug for NodeValue<State, Ctx> {
                 spacing,
                 align,
                 off_axis_align,
+                distribution,
             } => f
                 .debug_struct("Column")
                 .field("elements", elements)
                 .field("spacing", spacing)
                 .field("align", align)
                 .field("off_axis_align", off_axis_align)
+                .field("distribution", distribution)
                 .finish(),
             NodeValue::Row {
                 elements,
                 spacing,
                 align,
                 off_axis_align,
+                distribution,
             } => f
                 .debug_struct("Row")
                 .field("elements", elements)
                 .field("spacing", spacing)
                 .field("align", align)
                 .field("off_axis_align", off_axis_align)
+                .field("distribution", distribution)
                 .finish(),
             NodeValue::Stack {
                 elements,
@@ -43,7 +236,76 @@ impl<State, Ctx> fmt::Debug for NodeValue<State, Ctx> {
                 .field("x_align", x_align)
                 .field("y_align", y_align)
                 .finish(),
+            NodeValue::Wrap {
+                elements,
+                orientation,
+                spacing,
+                line_spacing,
+                x_align,
+                y_align,
+            } => f
+                .debug_struct("Wrap")
+                .field("elements", elements)
+                .field("orientation", orientation)
+                .field("spacing", spacing)
+                .field("line_spacing", line_spacing)
+                .field("x_align", x_align)
+                .field("y_align", y_align)
+                .finish(),
+            NodeValue::Grid {
+                elements,
+                columns,
+                rows,
+                column_spacing,
+                row_spacing,
+                x_align,
+                y_align,
+            } => f
+                .debug_struct("Grid")
+                .field("elements", elements)
+                .field("columns", columns)
+                .field("rows", rows)
+                .field("column_spacing", column_spacing)
+                .field("row_spacing", row_spacing)
+                .field("x_align", x_align)
+                .field("y_align", y_align)
+                .finish(),
             NodeValue::Group(elements) => f.debug_tuple("Group").field(elements).finish(),
+            NodeValue::Clip { element } => {
+                f.debug_struct("Clip").field("element", element).finish()
+            }
+            NodeValue::Direction { direction, element } => f
+                .debug_struct("Direction")
+                .field("direction", direction)
+                .field("element", element)
+                .finish(),
+            NodeValue::Animated {
+                id,
+                duration,
+                easing,
+                element,
+            } => f
+                .debug_struct("Animated")
+                .field("id", id)
+                .field("duration", duration)
+                .field("easing", easing)
+                .field("element", element)
+                .finish(),
+            NodeValue::Transition {
+                id,
+                transition,
+                element,
+            } => f
+                .debug_struct("Transition")
+                .field("id", id)
+                .field("transition", transition)
+                .field("element", element)
+                .finish(),
+            NodeValue::Identified { id, element, .. } => f
+                .debug_struct("Identified")
+                .field("id", id)
+                .field("element", element)
+                .finish(),
             NodeValue::Offset {
                 offset_x,
                 offset_y,
@@ -76,6 +338,34 @@ impl<State, Ctx> fmt::Debug for NodeValue<State, Ctx> {
                 .field("coupled", coupled)
                 .field("over", over)
                 .finish(),
+            NodeValue::Scroll {
+                axis,
+                viewport,
+                element,
+                ..
+            } => f
+                .debug_struct("Scroll")
+                .field("axis", axis)
+                .field("viewport", viewport)
+                .field("element", element)
+                .finish(),
+            NodeValue::Virtual {
+                orientation,
+                count,
+                extent,
+                extents,
+                overscan,
+                elements,
+                ..
+            } => f
+                .debug_struct("Virtual")
+                .field("orientation", orientation)
+                .field("count", count)
+                .field("extent", extent)
+                .field("measured", &extents.is_some())
+                .field("overscan", overscan)
+                .field("elements", elements)
+                .finish(),
         }
     }
 }
@@ -1,26 +1,54 @@
-use crate::models::Area;
-use std::{fmt, rc::Rc};
+use crate::{
+    models::Area,
+    shared::{shared_fn, Shared},
+    traits::{MaybeSync, Scalar},
+};
+use core::fmt;
 
-type DrawFn<State, Ctx> = Rc<dyn Fn(Area, &'_ mut State, &'_ mut Ctx)>;
+type DrawFn<State, Ctx, S> =
+    shared_fn!(Fn(Area<S>, Option<Area<S>>, f32, &'_ mut State, &'_ mut Ctx));
 
-#[derive(Clone)]
-pub(crate) struct Drawable<State, Ctx> {
-    pub(crate) area: Area,
-    pub(crate) draw: DrawFn<State, Ctx>,
+pub(crate) struct Drawable<State, Ctx, S = f32> {
+    pub(crate) area: Area<S>,
+    /// The opacity the node is drawn with, which is lowered by transitions (see [`crate::NodeWith::transition`])
+    pub(crate) opacity: f32,
+    pub(crate) draw: DrawFn<State, Ctx, S>,
 }
 
-impl<State, Ctx> Drawable<State, Ctx> {
-    pub(crate) fn draw(&self, area: Area, a: &mut State, b: &mut Ctx) {
-        if area.width > 0. && area.height > 0. {
-            (self.draw)(area, a, b);
+impl<State, Ctx, S: Scalar> Drawable<State, Ctx, S> {
+    pub(crate) fn new(
+        draw: impl Fn(Area<S>, Option<Area<S>>, f32, &mut State, &mut Ctx) + MaybeSync + 'static,
+    ) -> Self {
+        Self {
+            area: Area::default(),
+            opacity: 1.,
+            draw: Shared::new(draw),
+        }
+    }
+    pub(crate) fn draw(&self, area: Area<S>, clip: Option<Area<S>>, a: &mut State, b: &mut Ctx) {
+        // Nodes that are entirely clipped (like content scrolled out of view) aren't drawn
+        let visible = clip.map(|clip| area.intersection(clip)).unwrap_or(area);
+        if visible.width > S::ZERO && visible.height > S::ZERO {
+            (self.draw)(area, clip, self.opacity, a, b);
+        }
+    }
+}
+
+impl<State, Ctx, S: Scalar> Clone for Drawable<State, Ctx, S> {
+    fn clone(&self) -> Self {
+        Self {
+            area: self.area,
+            opacity: self.opacity,
+            draw: self.draw.clone(),
         }
     }
 }
 
-impl<State, Ctx> fmt::Debug for Drawable<State, Ctx> {
+impl<State, Ctx, S: Scalar> fmt::Debug for Drawable<State, Ctx, S> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Drawable")
             .field("area", &self.area)
+            .field("opacity", &self.opacity)
             .field("draw", &"<function>")
             .finish()
     }
@@ -0,0 +1,198 @@
+use crate::prelude::*;
+use crate::{
+    models::{Area, DebugNode, NodeConstraints, NodeKind},
+    traits::Scalar,
+    ComputedLayout,
+};
+use alloc::collections::BTreeMap;
+use core::fmt::{Display, Write};
+
+/// Colors for node outlines in SVG exports, chosen by depth
+const PALETTE: [&str; 6] = [
+    "#e6194b", "#3cb44b", "#4363d8", "#f58231", "#911eb4", "#42d4f4",
+];
+
+impl<State, Ctx, S: Scalar + Display> ComputedLayout<State, Ctx, S> {
+    /// Exports the computed tree as JSON, with the kind, id, area & constraints of every node.
+    ///
+    /// The output only changes when the layout does, so it can be compared against a saved copy in snapshot tests.
+    /// Ids are written as hex strings of their hash & areas are the areas allocated to each node (see [`DebugNode`]).
+    ///
+    /// ```rust
+    /// use backer::*;
+    /// use backer::models::*;
+    /// use backer::nodes::*;
+    ///
+    /// let computed = Layout::new(|_: &mut ()| draw(|_, _| {}).width(10.))
+    ///     .compute(Area::new(0., 0., 100., 100.), &mut ());
+    /// assert!(computed.to_json().starts_with(r#"{
+    ///   "kind": "Explicit",
+    ///   "id": null,
+    ///   "area": { "x": 45, "y": 0, "width": 10, "height": 100 },"#));
+    /// ```
+    pub fn to_json(&self) -> String {
+        let nodes = self.debug_nodes();
+        let mut json = String::new();
+        if !nodes.is_empty() {
+            write_json(&nodes, 0, 0, &mut json);
+        }
+        json.push('\n');
+        json
+    }
+    /// Exports the computed tree as an SVG image, with an outlined & labeled rectangle for the area of every node.
+    ///
+    /// Like [`ComputedLayout::to_json`], this is intended for snapshot tests, where the image can be reviewed
+    /// without running a UI.
+    pub fn to_svg(&self) -> String {
+        let nodes = self.debug_nodes();
+        let bounds = nodes.first().map(|node| node.area).unwrap_or_default();
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}" width="{}" height="{}">"#,
+            number(bounds.x),
+            number(bounds.y),
+            number(bounds.width),
+            number(bounds.height),
+            number(bounds.width),
+            number(bounds.height),
+        );
+        // Labels of nodes that start at the same point are placed on separate lines
+        let mut lines: BTreeMap<(u64, u64), usize> = BTreeMap::new();
+        for node in &nodes {
+            let Area {
+                x,
+                y,
+                width,
+                height,
+            } = node.area;
+            let color = PALETTE[node.depth % PALETTE.len()];
+            let fill = if node.kind == NodeKind::Draw {
+                color
+            } else {
+                "none"
+            };
+            let _ = writeln!(
+                svg,
+                r#"  <rect x="{}" y="{}" width="{}" height="{}" fill="{fill}" fill-opacity="0.1" stroke="{color}" stroke-width="1"/>"#,
+                number(x),
+                number(y),
+                number(width.max(S::ZERO)),
+                number(height.max(S::ZERO)),
+            );
+            let line = lines.entry((x.to_bits(), y.to_bits())).or_default();
+            let _ = writeln!(
+                svg,
+                r#"  <text x="{}" y="{}" font-family="monospace" font-size="8" fill="{color}">{}</text>"#,
+                number(x + S::from_f64(2.)),
+                number(y + S::from_f64(9.) + S::from_f64(*line as f64 * 9.)),
+                label(node),
+            );
+            *line += 1;
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+/// Writes the node at `index` & its descendants, returning the index of the node after them
+fn write_json<S: Scalar + Display>(
+    nodes: &[DebugNode<S>],
+    index: usize,
+    indent: usize,
+    json: &mut String,
+) -> usize {
+    let node = &nodes[index];
+    let pad = "  ".repeat(indent);
+    let Area {
+        x,
+        y,
+        width,
+        height,
+    } = node.area;
+    let NodeConstraints {
+        width_min,
+        width_max,
+        height_min,
+        height_max,
+        aspect,
+        expand_x,
+        expand_y,
+    } = node.constraints;
+    let _ = writeln!(json, "{{");
+    let _ = writeln!(json, r#"{pad}  "kind": "{}","#, kind_name(node.kind));
+    let _ = writeln!(
+        json,
+        r#"{pad}  "id": {},"#,
+        node.id
+            .map(|id| format!(r#""{id:#018x}""#))
+            .unwrap_or("null".to_string())
+    );
+    let _ = writeln!(
+        json,
+        r#"{pad}  "area": {{ "x": {}, "y": {}, "width": {}, "height": {} }},"#,
+        number(x),
+        number(y),
+        number(width),
+        number(height)
+    );
+    let _ = writeln!(
+        json,
+        r#"{pad}  "constraints": {{ "width_min": {}, "width_max": {}, "height_min": {}, "height_max": {}, "aspect": {}, "expand_x": {expand_x}, "expand_y": {expand_y} }},"#,
+        optional_number(width_min),
+        optional_number(width_max),
+        optional_number(height_min),
+        optional_number(height_max),
+        optional_number(aspect),
+    );
+    let mut next = index + 1;
+    let mut children = Vec::new();
+    while next < nodes.len() && nodes[next].depth > node.depth {
+        let mut child = String::new();
+        next = write_json(nodes, next, indent + 2, &mut child);
+        children.push(child);
+    }
+    if children.is_empty() {
+        let _ = writeln!(json, r#"{pad}  "children": []"#);
+    } else {
+        let _ = writeln!(json, r#"{pad}  "children": ["#);
+        let last = children.len() - 1;
+        for (i, child) in children.into_iter().enumerate() {
+            let _ = write!(json, "{pad}    {child}");
+            let _ = writeln!(json, "{}", if i < last { "," } else { "" });
+        }
+        let _ = writeln!(json, "{pad}  ]");
+    }
+    let _ = write!(json, "{pad}}}");
+    next
+}
+
+fn kind_name(kind: NodeKind) -> String {
+    match kind {
+        NodeKind::Coupled { over: true } => "CoupledOver".to_string(),
+        NodeKind::Coupled { over: false } => "CoupledUnder".to_string(),
+        kind => format!("{kind:?}"),
+    }
+}
+
+fn label<S>(node: &DebugNode<S>) -> String {
+    match node.id {
+        Some(id) => format!("{} {id:#018x}", kind_name(node.kind)),
+        None => kind_name(node.kind),
+    }
+}
+
+/// Formats a number consistently, without a negative sign on zero
+fn number<S: Scalar + Display>(value: S) -> String {
+    if !value.is_finite() {
+        return "null".to_string();
+    }
+    if value == S::ZERO {
+        return "0".to_string();
+    }
+    format!("{value}")
+}
+
+fn optional_number<S: Scalar + Display>(value: Option<S>) -> String {
+    value.map(number).unwrap_or("null".to_string())
+}
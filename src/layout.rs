@@ -1,9 +1,19 @@
+use crate::prelude::*;
 use crate::{
-    constraints::SizeConstraints, drawable::Drawable, models::*, node_cache::NodeCache,
-    traits::NodeTrait, Node, NodeWith,
+    animation::Animator,
+    constraints::{Constraint, SizeConstraints},
+    drawable::Drawable,
+    models::*,
+    node_cache::NodeCache,
+    retained::{reconcile, RetainedNode},
+    shared::{boxed_fn, lock, shared_fn, Lock, Shared},
+    traits::{MaybeSync, NodeTrait, Scalar},
+    transition::Transitions,
+    Node, NodeWith,
 };
+use alloc::collections::{BTreeSet, VecDeque};
 use core::f32;
-use std::{fmt::Debug, rc::Rc};
+use core::{fmt::Debug, hash::Hash, time::Duration};
 
 /**
 The root object used to store & calculate a layout
@@ -41,146 +51,658 @@ fn my_layout_fn(state: &mut MyState) -> Node<MyState> {
 struct MyState {}
 ```
  */
-pub struct Layout<State, Ctx> {
-    tree: LayoutFn<State, Ctx>,
+pub struct Layout<State, Ctx, S = f32> {
+    tree: LayoutFn<State, Ctx, S>,
+    debug: Option<DebugFn<State, Ctx, S>>,
+    debugging: bool,
+    on_conflict: Option<ConflictFn<S>>,
+    direction: Direction,
+    scale_factor: Option<S>,
+    cells: bool,
+    retain: bool,
+    frame: Lock<FrameState<State, Ctx, S>>,
 }
 
-pub type LayoutFn<State, Ctx> = Box<dyn Fn(&mut State, &mut Ctx) -> NodeWith<State, Ctx>>;
+/// What a [`Layout`] keeps from one frame to the next
+struct FrameState<State, Ctx, S> {
+    retained: Option<RetainedNode<S>>,
+    animator: Animator<S>,
+    transitions: Transitions<State, Ctx, S>,
+}
+
+pub type LayoutFn<State, Ctx, S = f32> =
+    boxed_fn!(Fn(&mut State, &mut Ctx) -> NodeWith<State, Ctx, S>);
+type DebugFn<State, Ctx, S> = shared_fn!(Fn(&DebugNode<S>, &mut State, &mut Ctx));
+type ConflictFn<S> = boxed_fn!(Fn(&ConstraintConflict<S>));
 
-impl<State, Ctx> Layout<State, Ctx> {
+impl<State, Ctx, S: Scalar> Layout<State, Ctx, S> {
     /// Creates a new [`Layout<State, Ctx>`].
-    pub fn new_with(tree: impl Fn(&mut State, &mut Ctx) -> NodeWith<State, Ctx> + 'static) -> Self {
+    pub fn new_with(
+        tree: impl Fn(&mut State, &mut Ctx) -> NodeWith<State, Ctx, S> + MaybeSync + 'static,
+    ) -> Self {
         Self {
             tree: Box::new(tree),
+            debug: None,
+            debugging: false,
+            on_conflict: None,
+            direction: Direction::default(),
+            scale_factor: None,
+            cells: false,
+            retain: false,
+            frame: Lock::new(FrameState {
+                retained: None,
+                animator: Animator::default(),
+                transitions: Transitions::new(),
+            }),
         }
     }
+    /// Keeps layout results between frames, so only the parts of the tree that changed are laid out again.
+    ///
+    /// Each frame, the newly built tree is compared against the previous one.
+    /// Nodes are matched by id (see [`NodeWith::id`]) where possible, & by position otherwise.
+    /// Subtrees are laid out again if any of their parameters changed, or if they contain
+    /// nodes that depend on closures - like [`NodeWith::dynamic_height`], [`crate::nodes::area_reader`]
+    /// & [`crate::nodes::scope`] - since closures can't be compared between frames.
+    pub fn retained(mut self) -> Self {
+        self.retain = true;
+        self
+    }
+    /// Sets the direction content is laid out in, which is left to right by default.
+    ///
+    /// The direction can be overridden for part of the layout with [`NodeWith::direction`].
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+    /// Changes the direction content is laid out in (see [`Layout::direction`])
+    pub fn set_direction(&mut self, direction: Direction) {
+        self.direction = direction;
+    }
+    /// Converts every computed area to device pixels for a display with `scale_factor` device pixels per unit,
+    /// with edges rounded to whole pixels (see [`Area::to_pixels`]).
+    ///
+    /// Layout is calculated in units as usual, so the areas passed to draw nodes, the debug overlay & [`ComputedLayout`]
+    /// are in device pixels. Adjacent nodes share edges exactly, so lines between them are sharp at any scale factor.
+    ///
+    /// ```rust
+    /// use backer::*;
+    /// use backer::models::*;
+    /// use backer::nodes::*;
+    ///
+    /// let computed = Layout::new(|_: &mut ()| {
+    ///     row(vec![
+    ///         draw(|_, _| {}).id("left"),
+    ///         draw(|_, _| {}).width(1.).id("divider"),
+    ///         draw(|_, _| {}).id("right"),
+    ///     ])
+    /// })
+    /// .pixel_snapping(1.25)
+    /// .compute(Area::new(0., 0., 100., 10.), &mut ());
+    /// let (left, divider, right) = (
+    ///     computed.area("left").unwrap(),
+    ///     computed.area("divider").unwrap(),
+    ///     computed.area("right").unwrap(),
+    /// );
+    /// assert_eq!(left.x + left.width, divider.x);
+    /// assert_eq!(divider.width, 1.);
+    /// assert_eq!(divider.x + divider.width, right.x);
+    /// assert_eq!(right.x + right.width, 125.);
+    /// ```
+    pub fn pixel_snapping(mut self, scale_factor: S) -> Self {
+        self.scale_factor = Some(scale_factor);
+        self
+    }
+    /// Changes the scale factor areas are converted to device pixels with, or turns conversion off with `None`
+    /// (see [`Layout::pixel_snapping`])
+    pub fn set_pixel_snapping(&mut self, scale_factor: Option<S>) {
+        self.scale_factor = scale_factor;
+    }
+    /// Lays out in whole character cells, for terminal UIs.
+    ///
+    /// Space left over when dividing cells between the elements of a sequence is handed out one cell at a time,
+    /// to the elements that lost the most to rounding first, & then in order. Every computed area is made of whole cells
+    /// & can be converted with [`Area::to_cells`]. This replaces [`Layout::pixel_snapping`].
+    ///
+    /// ```rust
+    /// use backer::*;
+    /// use backer::models::*;
+    /// use backer::nodes::*;
+    ///
+    /// let computed = Layout::new(|_: &mut ()| {
+    ///     row(vec![
+    ///         draw(|_, _| {}).id("a"),
+    ///         draw(|_, _| {}).id("b"),
+    ///         draw(|_, _| {}).id("c"),
+    ///     ])
+    /// })
+    /// .cells()
+    /// .compute(Area::new(0., 0., 10., 1.), &mut ());
+    /// assert_eq!(computed.area("a").unwrap().to_cells(), CellRect::new(0, 0, 4, 1));
+    /// assert_eq!(computed.area("b").unwrap().to_cells(), CellRect::new(4, 0, 3, 1));
+    /// assert_eq!(computed.area("c").unwrap().to_cells(), CellRect::new(7, 0, 3, 1));
+    /// ```
+    pub fn cells(mut self) -> Self {
+        self.cells = true;
+        self
+    }
+    /// Calls `overlay` for every node after drawing, to draw debug information over the layout (see [`Layout::debug`])
+    pub fn debug_with(
+        mut self,
+        overlay: impl Fn(&DebugNode<S>, &mut State, &mut Ctx) + MaybeSync + 'static,
+    ) -> Self {
+        self.debug = Some(Shared::new(overlay));
+        self.debugging = true;
+        self
+    }
+    /// Calls `hook` for each node with conflicting size limits every time the layout is computed
+    /// (see [`ComputedLayout::conflicts`])
+    pub fn on_conflict(
+        mut self,
+        hook: impl Fn(&ConstraintConflict<S>) + MaybeSync + 'static,
+    ) -> Self {
+        self.on_conflict = Some(Box::new(hook));
+        self
+    }
+    /// Turns the debug overlay set with [`Layout::debug`] on or off
+    pub fn set_debugging(&mut self, debugging: bool) {
+        self.debugging = debugging;
+    }
+    /// Sets the current time, measured from any fixed point, which drives animations
+    /// (see [`NodeWith::animate`] & [`NodeWith::transition`])
+    pub fn set_time(&mut self, time: Duration) {
+        lock(&self.frame).animator.time = time;
+    }
+    /// Whether any animations are in progress at the current time (see [`Layout::set_time`]),
+    /// in which case the layout should be drawn again in the next frame
+    pub fn is_animating(&self) -> bool {
+        let frame = lock(&self.frame);
+        frame.animator.is_animating() || frame.transitions.is_transitioning(frame.animator.time)
+    }
 }
 
-impl<State> Layout<State, ()> {
+impl<State, S: Scalar> Layout<State, (), S> {
     /// Creates a new [`Layout<State, Ctx>`].
-    pub fn new(tree: impl Fn(&mut State) -> Node<State> + 'static) -> Self {
-        Self {
-            tree: Box::new(move |state, _| tree(state)),
-        }
+    pub fn new(tree: impl Fn(&mut State) -> Node<State, S> + MaybeSync + 'static) -> Self {
+        Self::new_with(move |state, _| tree(state))
+    }
+    /// Calls `overlay` for every node after drawing, to draw debug information over the layout.
+    ///
+    /// Nodes are passed parents first, with the area they were allocated, their size constraints,
+    /// & the gaps left by padding & spacing, which helps to find out why a layout isn't as expected.
+    /// The overlay can be turned on & off with [`Layout::set_debugging`].
+    ///
+    /// ```rust
+    /// use backer::*;
+    /// use backer::models::*;
+    /// use backer::nodes::*;
+    ///
+    /// let layout = Layout::new(|_: &mut Vec<DebugNode>| {
+    ///     column_spaced(10., vec![draw(|_, _| {}), draw(|_, _| {}).pad(5.)])
+    /// })
+    /// .debug(|node, nodes| nodes.push(node.clone()));
+    /// let mut nodes = Vec::new();
+    /// layout.draw(Area::new(0., 0., 100., 110.), &mut nodes);
+    /// assert_eq!(nodes[0].kind, NodeKind::Column);
+    /// assert_eq!(nodes[0].spacing, vec![Area::new(0., 50., 100., 10.)]);
+    /// assert_eq!(nodes[2].kind, NodeKind::Padding);
+    /// assert_eq!(nodes[2].padding.len(), 4);
+    /// ```
+    pub fn debug(self, overlay: impl Fn(&DebugNode<S>, &mut State) + MaybeSync + 'static) -> Self {
+        self.debug_with(move |node, state, _| overlay(node, state))
     }
 }
 
-impl<State> Layout<State, ()> {
+impl<State, S: Scalar> Layout<State, (), S> {
     /// Calculates layout and draws all draw nodes in the tree
-    pub fn draw(&self, area: Area, state: &mut State) {
-        let ctx = &mut ();
-        let mut layout = (self.tree)(state, ctx);
-        let constraints = layout.inner.constraints(area, state, ctx);
-        layout.inner.layout(
-            area.constrained(&constraints, XAlign::Center, YAlign::Center),
-            None,
-            None,
-            state,
-            ctx,
-        );
-        layout.inner.draw(state, ctx);
+    pub fn draw(&self, area: Area<S>, state: &mut State) {
+        self.compute(area, state).draw(state);
+    }
+    /// Calculates layout without drawing (see [`ComputedLayout`])
+    pub fn compute(&self, area: Area<S>, state: &mut State) -> ComputedLayout<State, (), S> {
+        self.compute_with(area, state, &mut ())
+    }
+    /// Calculates layout and draws all draw nodes in the tree, or returns an error without drawing
+    /// if the layout is invalid.
+    ///
+    /// ```rust
+    /// use backer::*;
+    /// use backer::models::*;
+    /// use backer::nodes::*;
+    ///
+    /// let layout = Layout::new(|_: &mut ()| {
+    ///     row(vec![draw(|_, _| {}).id("a"), draw(|_, _| {}).id("a")])
+    /// });
+    /// let result = layout.try_draw(Area::new(0., 0., 100., 100.), &mut ());
+    /// assert!(matches!(result, Err(LayoutError::DuplicateId(_))));
+    /// ```
+    pub fn try_draw(&self, area: Area<S>, state: &mut State) -> Result<(), LayoutError<S>> {
+        self.try_draw_with(area, state, &mut ())
+    }
+    /// Calculates layout without drawing, or returns an error if the layout is invalid (see [`Layout::try_draw`])
+    pub fn try_compute(
+        &self,
+        area: Area<S>,
+        state: &mut State,
+    ) -> Result<ComputedLayout<State, (), S>, LayoutError<S>> {
+        self.try_compute_with(area, state, &mut ())
     }
 }
 
-impl<State, Ctx> Layout<State, Ctx> {
+impl<State, Ctx, S: Scalar> Layout<State, Ctx, S> {
     /// Calculates layout and draws all draw nodes in the tree
-    pub fn draw_with(&self, area: Area, state: &mut State, ctx: &mut Ctx) {
-        let mut layout = (self.tree)(state, ctx);
-        let constraints = layout.inner.constraints(area, state, ctx);
-        layout.inner.layout(
-            area.constrained(&constraints, XAlign::Center, YAlign::Center),
-            None,
-            None,
-            state,
-            ctx,
-        );
-        layout.inner.draw(state, ctx);
+    pub fn draw_with(&self, area: Area<S>, state: &mut State, ctx: &mut Ctx) {
+        self.compute_with(area, state, ctx).draw_with(state, ctx);
+    }
+    /// Calculates layout without drawing (see [`ComputedLayout`])
+    pub fn compute_with(
+        &self,
+        area: Area<S>,
+        state: &mut State,
+        ctx: &mut Ctx,
+    ) -> ComputedLayout<State, Ctx, S> {
+        match self.compute_frame(area, state, ctx, cfg!(debug_assertions)) {
+            Ok(computed) => computed,
+            Err(error) => panic!("{error}"),
+        }
+    }
+    /// Calculates layout and draws all draw nodes in the tree, or returns an error without drawing
+    /// if the layout is invalid (see [`Layout::try_draw`])
+    pub fn try_draw_with(
+        &self,
+        area: Area<S>,
+        state: &mut State,
+        ctx: &mut Ctx,
+    ) -> Result<(), LayoutError<S>> {
+        self.try_compute_with(area, state, ctx)?
+            .draw_with(state, ctx);
+        Ok(())
+    }
+    /// Calculates layout without drawing, or returns an error if the layout is invalid (see [`Layout::try_draw`])
+    ///
+    /// Unlike [`Layout::compute_with`], which only checks for duplicate ids in debug builds,
+    /// the layout is always checked.
+    pub fn try_compute_with(
+        &self,
+        area: Area<S>,
+        state: &mut State,
+        ctx: &mut Ctx,
+    ) -> Result<ComputedLayout<State, Ctx, S>, LayoutError<S>> {
+        let Area {
+            x,
+            y,
+            width,
+            height,
+        } = area;
+        if [x, y, width, height].iter().any(|value| !value.is_finite())
+            || width < S::ZERO
+            || height < S::ZERO
+        {
+            return Err(LayoutError::InvalidArea(area));
+        }
+        self.compute_frame(area, state, ctx, true)
+    }
+    /// Lays out a frame, checking for duplicate ids first if `check_ids` is set,
+    /// so the layout's retained areas, animations & transitions are only updated by valid frames
+    fn compute_frame(
+        &self,
+        area: Area<S>,
+        state: &mut State,
+        ctx: &mut Ctx,
+        check_ids: bool,
+    ) -> Result<ComputedLayout<State, Ctx, S>, LayoutError<S>> {
+        let cells = self.cells;
+        let area = if cells { area.to_pixels(S::ONE) } else { area };
+        let mut layout = NodeCache::new((self.tree)(state, ctx).inner);
+        // The frame state is only locked while it's used, since closures in the tree could use the layout
+        let ids = {
+            let frame = lock(&self.frame);
+            let ids = frame
+                .transitions
+                .insert_leaving(&mut layout, frame.animator.time);
+            if self.retain {
+                reconcile(&mut layout, frame.retained.as_ref());
+            }
+            ids
+        };
+        let constraints = layout.constraints(area, cells, state, ctx);
+        let visible = area;
+        let area = area.constrained(&constraints, XAlign::Center, YAlign::Center);
+        layout.layout(area, None, None, visible, self.direction, cells, state, ctx);
+        if let Some(id) = check_ids
+            .then(|| layout.duplicate_id(&mut BTreeSet::new()))
+            .flatten()
+        {
+            return Err(LayoutError::DuplicateId(id));
+        }
+        {
+            let mut frame = lock(&self.frame);
+            let frame = &mut *frame;
+            if self.retain {
+                frame.retained = Some(RetainedNode::snapshot(&layout));
+            }
+            // Animations are applied after the snapshot, so retained areas are the areas nodes animate towards
+            layout.animate(&mut frame.animator);
+            frame.animator.finish_frame();
+            frame
+                .transitions
+                .apply(&mut layout, &ids, frame.animator.time);
+        }
+        // Distributing whole cells doesn't cover alignment or padding, so cell layouts are snapped to cells too
+        let area = match self
+            .scale_factor
+            .filter(|_| !cells)
+            .or(cells.then_some(S::ONE))
+        {
+            Some(scale_factor) => {
+                layout.map_areas(&|area| area.to_pixels(scale_factor));
+                area.to_pixels(scale_factor)
+            }
+            None => area,
+        };
+        let root = layout.kind.computed(area);
+        let computed = ComputedLayout {
+            root,
+            layout,
+            debug: self.debug.clone().filter(|_| self.debugging),
+        };
+        if let Some(hook) = &self.on_conflict {
+            computed.conflicts().iter().for_each(hook);
+        }
+        Ok(computed)
+    }
+}
+
+/**
+The result of calculating a layout with [`Layout::compute`]
+
+Computing a layout doesn't call any draw closures,
+so the result can be inspected, drawn later, or drawn multiple times.
+
+```rust
+use backer::*;
+use backer::models::*;
+use backer::nodes::*;
+
+let layout = Layout::new(|_: &mut ()| {
+    row(vec![
+        draw(|_, _| {}).width(10.),
+        draw(|_, _| {}),
+    ])
+});
+let mut computed = layout.compute(Area::new(0., 0., 100., 100.), &mut ());
+assert_eq!(computed.root().kind, NodeKind::Row);
+assert_eq!(computed.root().children[1].area, Area::new(10., 0., 90., 100.));
+computed.draw(&mut ());
+```
+ */
+pub struct ComputedLayout<State, Ctx, S = f32> {
+    pub(crate) layout: NodeCache<State, Ctx, S>,
+    root: ComputedNode<S>,
+    debug: Option<DebugFn<State, Ctx, S>>,
+}
+
+impl<State, S: Scalar> ComputedLayout<State, (), S> {
+    /// Draws all draw nodes in the tree, using the computed areas
+    pub fn draw(&mut self, state: &mut State) {
+        self.draw_with(state, &mut ());
+    }
+}
+
+impl<State, Ctx, S: Scalar> ComputedLayout<State, Ctx, S> {
+    /// Draws all draw nodes in the tree, using the computed areas
+    pub fn draw_with(&mut self, state: &mut State, ctx: &mut Ctx) {
+        self.layout.draw(None, state, ctx);
+        if let Some(debug) = &self.debug {
+            self.debug_nodes()
+                .iter()
+                .for_each(|node| debug(node, state, ctx));
+        }
+    }
+    /// Every node with a minimum size larger than its maximum size, which were resolved by using the minimum size.
+    ///
+    /// ```rust
+    /// use backer::*;
+    /// use backer::models::*;
+    /// use backer::nodes::*;
+    ///
+    /// let computed = Layout::new(|_: &mut ()| {
+    ///     row(vec![
+    ///         draw(|_, _| {}),
+    ///         draw(|_, _| {}).width_range(20.0..10.).id("conflicted"),
+    ///     ])
+    /// })
+    /// .compute(Area::new(0., 0., 100., 100.), &mut ());
+    /// let conflicts = computed.conflicts();
+    /// assert_eq!(conflicts[0].path, vec![1, 0]);
+    /// assert_eq!(conflicts[0].dimension, Dimension::Width);
+    /// assert_eq!((conflicts[0].min, conflicts[0].max), (20., 10.));
+    /// assert_eq!(computed.area("conflicted").unwrap().width, 20.);
+    /// ```
+    pub fn conflicts(&self) -> Vec<ConstraintConflict<S>> {
+        let mut conflicts = Vec::new();
+        self.layout
+            .collect_conflicts(&mut Vec::new(), None, &mut conflicts);
+        conflicts
+    }
+    /// The layout of every node, parents first, as passed to the debug overlay (see [`Layout::debug`])
+    pub fn debug_nodes(&self) -> Vec<DebugNode<S>> {
+        let mut nodes = Vec::new();
+        self.layout.collect_debug(0, &mut nodes);
+        nodes
+    }
+    /// The root node of the computed layout
+    pub fn root(&self) -> &ComputedNode<S> {
+        &self.root
+    }
+    /// Finds every identified node (see [`NodeWith::id`]) containing the point at `x`, `y`,
+    /// ordered from the last drawn (top-most) node to the first drawn node.
+    ///
+    /// ```rust
+    /// use backer::*;
+    /// use backer::models::*;
+    /// use backer::nodes::*;
+    ///
+    /// let computed = Layout::new(|_: &mut ()| {
+    ///     stack(vec![
+    ///         draw(|_, _| {}).id("background"),
+    ///         draw(|_, _| {}).width(10.).height(10.).id("button"),
+    ///     ])
+    /// })
+    /// .compute(Area::new(0., 0., 100., 100.), &mut ());
+    /// let hits = computed.hit_test(50., 50.);
+    /// assert!(hits[0].has_id("button"));
+    /// assert!(hits[1].has_id("background"));
+    /// ```
+    pub fn hit_test(&self, x: S, y: S) -> Vec<&ComputedNode<S>> {
+        self.root.hit_test(x, y)
+    }
+    /// The area of the node identified by `id` (see [`NodeWith::id`])
+    ///
+    /// If nodes in different scopes share an id, the first one in the order the nodes were defined is used.
+    pub fn area(&self, id: impl Hash) -> Option<Area<S>> {
+        self.root.find(id).map(|node| node.area)
+    }
+}
+
+impl<State, Ctx, S: Scalar> Debug for ComputedLayout<State, Ctx, S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ComputedLayout")
+            .field("root", &self.root)
+            .finish()
     }
 }
 
-type AreaReaderFn<State, Ctx> = Rc<dyn Fn(Area, &mut State, &mut Ctx) -> NodeWith<State, Ctx>>;
+type AreaReaderFn<State, Ctx, S> =
+    shared_fn!(Fn(Area<S>, &mut State, &mut Ctx) -> NodeWith<State, Ctx, S>);
+type ScrollFn<State, Ctx, S> = shared_fn!(Fn(ScrollInfo<S>, &mut State, &mut Ctx) -> (S, S));
+type VirtualFn<State, Ctx, S> =
+    shared_fn!(Fn(usize, &mut State, &mut Ctx) -> NodeWith<State, Ctx, S>);
+pub(crate) type ExtentFn<State, Ctx, S> = shared_fn!(Fn(usize, &mut State, &mut Ctx) -> S);
 
-pub(crate) enum NodeValue<State, Ctx> {
+pub(crate) enum NodeValue<State, Ctx, S> {
     Padding {
-        amounts: Padding,
-        element: Box<NodeCache<State, Ctx>>,
+        amounts: Padding<S>,
+        element: Box<NodeCache<State, Ctx, S>>,
     },
     Column {
-        elements: Vec<NodeCache<State, Ctx>>,
-        spacing: f32,
+        elements: Vec<NodeCache<State, Ctx, S>>,
+        spacing: S,
         align: Option<YAlign>,
         off_axis_align: Option<XAlign>,
+        distribution: Option<Distribution>,
     },
     Row {
-        elements: Vec<NodeCache<State, Ctx>>,
-        spacing: f32,
+        elements: Vec<NodeCache<State, Ctx, S>>,
+        spacing: S,
         align: Option<XAlign>,
         off_axis_align: Option<YAlign>,
+        distribution: Option<Distribution>,
     },
     Stack {
-        elements: Vec<NodeCache<State, Ctx>>,
+        elements: Vec<NodeCache<State, Ctx, S>>,
         x_align: Option<XAlign>,
         y_align: Option<YAlign>,
     },
-    Group(Vec<NodeCache<State, Ctx>>),
+    Wrap {
+        elements: Vec<NodeCache<State, Ctx, S>>,
+        orientation: Orientation,
+        spacing: S,
+        line_spacing: S,
+        x_align: Option<XAlign>,
+        y_align: Option<YAlign>,
+    },
+    Grid {
+        elements: Vec<NodeCache<State, Ctx, S>>,
+        columns: Vec<Track<S>>,
+        rows: Vec<Track<S>>,
+        column_spacing: S,
+        row_spacing: S,
+        x_align: Option<XAlign>,
+        y_align: Option<YAlign>,
+    },
+    Group(Vec<NodeCache<State, Ctx, S>>),
+    Identified {
+        id: u64,
+        element: Box<NodeCache<State, Ctx, S>>,
+    },
+    Clip {
+        element: Box<NodeCache<State, Ctx, S>>,
+    },
+    Direction {
+        direction: Direction,
+        element: Box<NodeCache<State, Ctx, S>>,
+    },
+    Animated {
+        id: Option<u64>,
+        duration: Duration,
+        easing: Easing,
+        element: Box<NodeCache<State, Ctx, S>>,
+    },
+    Transition {
+        id: Option<u64>,
+        transition: Transition,
+        element: Box<NodeCache<State, Ctx, S>>,
+    },
     Offset {
-        offset_x: f32,
-        offset_y: f32,
-        element: Box<NodeCache<State, Ctx>>,
+        offset_x: S,
+        offset_y: S,
+        element: Box<NodeCache<State, Ctx, S>>,
     },
-    Draw(Drawable<State, Ctx>),
+    Draw(Drawable<State, Ctx, S>),
     Explicit {
-        options: Size<State, Ctx>,
-        element: Box<NodeCache<State, Ctx>>,
+        options: Size<State, Ctx, S>,
+        element: Box<NodeCache<State, Ctx, S>>,
     },
     Empty,
     Space,
     Scope {
-        scoped: Box<dyn NodeTrait<State, Ctx>>,
+        scoped: Box<dyn NodeTrait<State, Ctx, S>>,
     },
     AreaReader {
-        read: AreaReaderFn<State, Ctx>,
+        read: AreaReaderFn<State, Ctx, S>,
     },
     Coupled {
         over: bool,
-        element: Box<NodeCache<State, Ctx>>,
-        coupled: Box<NodeCache<State, Ctx>>,
+        element: Box<NodeCache<State, Ctx, S>>,
+        coupled: Box<NodeCache<State, Ctx, S>>,
+    },
+    Scroll {
+        axis: ScrollAxis,
+        offset: ScrollFn<State, Ctx, S>,
+        viewport: Area<S>,
+        element: Box<NodeCache<State, Ctx, S>>,
+    },
+    Virtual {
+        orientation: Orientation,
+        count: usize,
+        extent: S,
+        /// The extent of each element, which replaces `extent` when it's set
+        extents: Option<ExtentFn<State, Ctx, S>>,
+        overscan: usize,
+        build: VirtualFn<State, Ctx, S>,
+        elements: Vec<NodeCache<State, Ctx, S>>,
     },
 }
 
-impl<State, Ctx> NodeValue<State, Ctx> {
-    pub(crate) fn draw(&mut self, state: &mut State, ctx: &mut Ctx) {
+impl<State, Ctx, S: Scalar> NodeValue<State, Ctx, S> {
+    pub(crate) fn draw(&mut self, clip: Option<Area<S>>, state: &mut State, ctx: &mut Ctx) {
         match self {
-            NodeValue::Draw(drawable) => drawable.draw(drawable.area, state, ctx),
+            NodeValue::Draw(drawable) => drawable.draw(drawable.area, clip, state, ctx),
             NodeValue::Padding { element, .. }
             | NodeValue::Explicit { element, .. }
+            | NodeValue::Identified { element, .. }
+            | NodeValue::Direction { element, .. }
+            | NodeValue::Animated { element, .. }
+            | NodeValue::Transition { element, .. }
             | NodeValue::Offset { element, .. } => {
-                element.draw(state, ctx);
+                element.draw(clip, state, ctx);
             }
-            NodeValue::Stack { elements, .. } => {
-                elements.iter_mut().for_each(|el| el.draw(state, ctx));
+            NodeValue::Stack { elements, .. }
+            | NodeValue::Wrap { elements, .. }
+            | NodeValue::Grid { elements, .. }
+            | NodeValue::Virtual { elements, .. }
+            | NodeValue::Group(elements) => {
+                elements.iter_mut().for_each(|el| el.draw(clip, state, ctx));
             }
             NodeValue::Column { elements, .. } | NodeValue::Row { elements, .. } => {
-                elements.iter_mut().rev().for_each(|el| el.draw(state, ctx));
+                elements
+                    .iter_mut()
+                    .rev()
+                    .for_each(|el| el.draw(clip, state, ctx));
             }
-            NodeValue::Space => (),
-            NodeValue::Scope { scoped } => scoped.draw(state, ctx),
+            NodeValue::Space | NodeValue::Empty => (),
+            NodeValue::Scope { scoped } => scoped.draw(clip, state, ctx),
             NodeValue::Coupled {
                 element,
                 coupled,
                 over,
             } => {
                 if *over {
-                    element.draw(state, ctx);
-                    coupled.draw(state, ctx);
+                    element.draw(clip, state, ctx);
+                    coupled.draw(clip, state, ctx);
                 } else {
-                    coupled.draw(state, ctx);
-                    element.draw(state, ctx);
+                    coupled.draw(clip, state, ctx);
+                    element.draw(clip, state, ctx);
                 }
             }
-            NodeValue::Group(_) | NodeValue::Empty | NodeValue::AreaReader { .. } => {
-                unreachable!()
+            NodeValue::Clip { element } => {
+                let clip = match (clip, element.occupied_area()) {
+                    (Some(clip), Some(area)) => Some(clip.intersection(area)),
+                    (clip, area) => clip.or(area),
+                };
+                element.draw(clip, state, ctx);
+            }
+            NodeValue::Scroll {
+                viewport, element, ..
+            } => {
+                let clip = clip
+                    .map(|clip| clip.intersection(*viewport))
+                    .unwrap_or(*viewport);
+                element.draw(Some(clip), state, ctx);
             }
+            // Area readers are replaced by the node they return during layout,
+            // so one is only left in the tree if it was never laid out
+            NodeValue::AreaReader { .. } => (),
         }
     }
 
@@ -204,24 +726,26 @@ impl<State, Ctx> NodeValue<State, Ctx> {
 
     pub(crate) fn allocate_area(
         &mut self,
-        available_area: Area,
+        available_area: Area<S>,
         contextual_x_align: Option<XAlign>,
         contextual_y_align: Option<YAlign>,
+        cells: bool,
         state: &mut State,
         ctx: &mut Ctx,
-    ) -> Vec<Area> {
+    ) -> Vec<Area<S>> {
         match self {
             NodeValue::Padding { amounts, .. } => vec![Area {
                 x: available_area.x + amounts.leading,
                 y: available_area.y + amounts.top,
-                width: (available_area.width - amounts.trailing - amounts.leading).max(0.),
-                height: (available_area.height - amounts.bottom - amounts.top).max(0.),
+                width: (available_area.width - amounts.trailing - amounts.leading).max(S::ZERO),
+                height: (available_area.height - amounts.bottom - amounts.top).max(S::ZERO),
             }],
             NodeValue::Column {
                 elements,
                 spacing,
                 align,
                 off_axis_align,
+                distribution,
             } => layout_axis(
                 elements,
                 spacing,
@@ -229,15 +753,17 @@ impl<State, Ctx> NodeValue<State, Ctx> {
                 Orientation::Vertical,
                 off_axis_align.unwrap_or(XAlign::Center),
                 align.unwrap_or(YAlign::Center),
+                *distribution,
+                cells,
                 state,
                 ctx,
-                true,
             ),
             NodeValue::Row {
                 elements,
                 spacing,
                 align,
                 off_axis_align,
+                distribution,
             } => layout_axis(
                 elements,
                 spacing,
@@ -245,9 +771,10 @@ impl<State, Ctx> NodeValue<State, Ctx> {
                 Orientation::Horizontal,
                 align.unwrap_or(XAlign::Center),
                 off_axis_align.unwrap_or(YAlign::Center),
+                *distribution,
+                cells,
                 state,
                 ctx,
-                true,
             ),
             NodeValue::Stack {
                 elements,
@@ -257,12 +784,50 @@ impl<State, Ctx> NodeValue<State, Ctx> {
                 .iter_mut()
                 .map(|child| {
                     available_area.constrained(
-                        &child.constraints(available_area, state, ctx),
+                        &child.constraints(available_area, cells, state, ctx),
                         x_align.unwrap_or(XAlign::Center),
                         y_align.unwrap_or(YAlign::Center),
                     )
                 })
                 .collect(),
+            NodeValue::Wrap {
+                elements,
+                orientation,
+                spacing,
+                line_spacing,
+                x_align,
+                y_align,
+            } => layout_wrap(
+                elements,
+                *orientation,
+                (*spacing, *line_spacing),
+                available_area,
+                x_align.unwrap_or(XAlign::Center),
+                y_align.unwrap_or(YAlign::Center),
+                cells,
+                state,
+                ctx,
+            ),
+            NodeValue::Grid {
+                elements,
+                columns,
+                rows,
+                column_spacing,
+                row_spacing,
+                x_align,
+                y_align,
+            } => layout_grid(
+                elements,
+                columns,
+                rows,
+                (*column_spacing, *row_spacing),
+                available_area,
+                x_align.unwrap_or(XAlign::Center),
+                y_align.unwrap_or(YAlign::Center),
+                cells,
+                state,
+                ctx,
+            ),
             NodeValue::Explicit { options, .. } => {
                 vec![available_area.constrained(
                     &SizeConstraints::from_size(options.clone(), available_area, state, ctx),
@@ -278,34 +843,94 @@ impl<State, Ctx> NodeValue<State, Ctx> {
                 width: available_area.width,
                 height: available_area.height,
             }],
+            NodeValue::Scroll { axis, element, .. } => {
+                // The content is unbounded along the scroll axis, but always fills the viewport.
+                // It's scrolled by the offset when it's laid out, so the offset is only resolved once per frame.
+                let constraints = element.constraints(available_area, cells, state, ctx);
+                let width = if axis.horizontal() {
+                    available_area
+                        .width
+                        .max(constraints.width.get_lower().unwrap_or(S::ZERO))
+                } else {
+                    available_area.width
+                };
+                let height = if axis.vertical() {
+                    available_area
+                        .height
+                        .max(constraints.height.get_lower().unwrap_or(S::ZERO))
+                } else {
+                    available_area.height
+                };
+                vec![Area {
+                    x: available_area.x,
+                    y: available_area.y,
+                    width,
+                    height,
+                }]
+            }
             NodeValue::Draw(_)
             | NodeValue::Space
+            | NodeValue::Empty
+            | NodeValue::Virtual { .. }
+            | NodeValue::Identified { .. }
+            | NodeValue::Clip { .. }
+            | NodeValue::Direction { .. }
+            | NodeValue::Animated { .. }
+            | NodeValue::Transition { .. }
             | NodeValue::Scope { .. }
             | NodeValue::AreaReader { .. }
             | NodeValue::Coupled { .. } => {
                 vec![available_area]
             }
-            NodeValue::Group(_) | NodeValue::Empty => unreachable!(),
+            // Nodes in a group that isn't inside a sequence are stacked
+            NodeValue::Group(elements) => vec![available_area; elements.len()],
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn layout(
         &mut self,
-        available_area: Area,
+        available_area: Area<S>,
         contextual_x_align: Option<XAlign>,
         contextual_y_align: Option<YAlign>,
+        visible: Area<S>,
+        direction: Direction,
+        cells: bool,
         state: &mut State,
         ctx: &mut Ctx,
     ) {
         let contextual_aligns = self.contextual_aligns();
 
-        let allocated = self.allocate_area(
+        let mut allocated = self.allocate_area(
             available_area,
             contextual_aligns.0.or(contextual_x_align),
             contextual_aligns.1.or(contextual_y_align),
+            cells,
             state,
             ctx,
         );
+        if let NodeValue::Scroll { axis, offset, .. } = self {
+            let content = &mut allocated[0];
+            let info = ScrollInfo {
+                viewport: available_area,
+                content_width: content.width,
+                content_height: content.height,
+            };
+            let (offset_x, offset_y) = offset(info, state, ctx);
+            if axis.horizontal() {
+                content.x = content.x - offset_x.clamp(S::ZERO, info.max_offset_x());
+            }
+            if axis.vertical() {
+                content.y = content.y - offset_y.clamp(S::ZERO, info.max_offset_y());
+            }
+        }
+        // Right to left layout is left to right layout mirrored within each node's area.
+        // Offsets are absolute, so they aren't mirrored.
+        if direction == Direction::RightToLeft && !matches!(self, NodeValue::Offset { .. }) {
+            allocated
+                .iter_mut()
+                .for_each(|area| *area = area.mirrored(available_area));
+        }
 
         match self {
             NodeValue::Column {
@@ -324,48 +949,305 @@ impl<State, Ctx> NodeValue<State, Ctx> {
                     .iter_mut()
                     .zip(allocated)
                     .for_each(|(el, allocation)| {
-                        el.layout(allocation, *x_align, *y_align, state, ctx)
+                        el.layout(
+                            allocation, *x_align, *y_align, visible, direction, cells, state, ctx,
+                        )
                     });
             }
-            NodeValue::Stack { elements, .. } => {
+            NodeValue::Stack { elements, .. }
+            | NodeValue::Wrap { elements, .. }
+            | NodeValue::Grid { elements, .. } => {
                 elements
                     .iter_mut()
                     .zip(allocated)
-                    .for_each(|(el, allocation)| el.layout(allocation, None, None, state, ctx));
+                    .for_each(|(el, allocation)| {
+                        el.layout(
+                            allocation, None, None, visible, direction, cells, state, ctx,
+                        )
+                    });
             }
             NodeValue::Padding { element, .. }
             | NodeValue::Explicit { element, .. }
             | NodeValue::Offset { element, .. } => {
-                element.layout(allocated[0], None, None, state, ctx);
+                element.layout(
+                    allocated[0],
+                    None,
+                    None,
+                    visible,
+                    direction,
+                    cells,
+                    state,
+                    ctx,
+                );
+            }
+            NodeValue::Identified { element, .. } => {
+                element.layout(
+                    allocated[0],
+                    contextual_x_align,
+                    contextual_y_align,
+                    visible,
+                    direction,
+                    cells,
+                    state,
+                    ctx,
+                );
+            }
+            NodeValue::Direction { direction, element } => {
+                element.layout(
+                    allocated[0],
+                    contextual_x_align,
+                    contextual_y_align,
+                    visible,
+                    *direction,
+                    cells,
+                    state,
+                    ctx,
+                );
+            }
+            NodeValue::Animated { element, .. } | NodeValue::Transition { element, .. } => {
+                element.layout(
+                    allocated[0],
+                    contextual_x_align,
+                    contextual_y_align,
+                    visible,
+                    direction,
+                    cells,
+                    state,
+                    ctx,
+                );
+            }
+            NodeValue::Clip { element } => {
+                // Children are culled against the area they're clipped to when they're drawn
+                let clip = element.occupied_area_in(
+                    allocated[0],
+                    contextual_x_align,
+                    contextual_y_align,
+                    direction,
+                    cells,
+                    state,
+                    ctx,
+                );
+                element.layout(
+                    allocated[0],
+                    contextual_x_align,
+                    contextual_y_align,
+                    visible.intersection(clip),
+                    direction,
+                    cells,
+                    state,
+                    ctx,
+                );
             }
             NodeValue::Draw(drawable) => {
                 drawable.area = allocated[0];
-                drawable.area.width = drawable.area.width.max(0.);
-                drawable.area.height = drawable.area.height.max(0.);
+                drawable.area.width = drawable.area.width.max(S::ZERO);
+                drawable.area.height = drawable.area.height.max(S::ZERO);
             }
             NodeValue::Space => (),
             NodeValue::Scope { scoped } => {
-                scoped.layout(available_area, None, None, state, ctx);
+                scoped.layout(
+                    available_area,
+                    None,
+                    None,
+                    visible,
+                    direction,
+                    cells,
+                    state,
+                    ctx,
+                );
             }
             NodeValue::AreaReader { read } => {
                 *self = read(allocated[0], state, ctx).inner;
-                self.layout(allocated[0], None, None, state, ctx);
+                self.layout(
+                    allocated[0],
+                    None,
+                    None,
+                    visible,
+                    direction,
+                    cells,
+                    state,
+                    ctx,
+                );
             }
             NodeValue::Coupled {
                 element, coupled, ..
             } => {
-                element.layout(allocated[0], None, None, state, ctx);
-                coupled.layout(allocated[0], None, None, state, ctx);
+                element.layout(
+                    allocated[0],
+                    None,
+                    None,
+                    visible,
+                    direction,
+                    cells,
+                    state,
+                    ctx,
+                );
+                coupled.layout(
+                    allocated[0],
+                    None,
+                    None,
+                    visible,
+                    direction,
+                    cells,
+                    state,
+                    ctx,
+                );
+            }
+            NodeValue::Scroll {
+                viewport, element, ..
+            } => {
+                // Children are culled against the viewport they're clipped to when they're drawn
+                *viewport = available_area;
+                let visible = visible.intersection(*viewport);
+                element.layout(
+                    allocated[0],
+                    None,
+                    None,
+                    visible,
+                    direction,
+                    cells,
+                    state,
+                    ctx,
+                );
+            }
+            NodeValue::Virtual {
+                orientation,
+                count,
+                extent,
+                extents,
+                overscan,
+                build,
+                elements,
+            } => {
+                let area = allocated[0];
+                // Items are placed left to right, then mirrored in right to left layout
+                let mirror = direction == Direction::RightToLeft;
+                let ltr_visible = if mirror {
+                    visible.mirrored(area)
+                } else {
+                    visible
+                };
+                let (start, visible_start, visible_end) = match orientation {
+                    Orientation::Horizontal => {
+                        (area.x, ltr_visible.x, ltr_visible.x + ltr_visible.width)
+                    }
+                    Orientation::Vertical => (area.y, visible.y, visible.y + visible.height),
+                };
+                let (visible_start, visible_end) = (visible_start - start, visible_end - start);
+                let items = match extents {
+                    Some(extents) => {
+                        visible_items(*count, *overscan, visible_start, visible_end, |i| {
+                            extents(i, state, ctx)
+                        })
+                    }
+                    // Elements without an extent would all be visible at once
+                    None if *extent > S::ZERO => {
+                        let index = |offset: S| offset.max(S::ZERO).to_f64() as usize;
+                        let first = index((visible_start / *extent).floor())
+                            .saturating_sub(*overscan)
+                            .min(*count);
+                        // Rounded up, as the negated floor of the negated value
+                        let last = index(S::ZERO - (S::ZERO - visible_end / *extent).floor())
+                            .saturating_add(*overscan)
+                            .min(*count);
+                        (first..last)
+                            .map(|i| (i, S::from_f64(i as f64) * *extent, *extent))
+                            .collect()
+                    }
+                    None => Vec::new(),
+                };
+                // Only the items inside the visible area are built
+                *elements = items
+                    .into_iter()
+                    .filter_map(|(i, offset, extent)| {
+                        let element = build(i, state, ctx).inner;
+                        if let NodeValue::Empty = element {
+                            return None;
+                        }
+                        let offset = start + offset;
+                        let item_area = match orientation {
+                            Orientation::Horizontal => Area {
+                                x: offset,
+                                width: extent,
+                                ..area
+                            },
+                            Orientation::Vertical => Area {
+                                y: offset,
+                                height: extent,
+                                ..area
+                            },
+                        };
+                        let item_area = if mirror {
+                            item_area.mirrored(area)
+                        } else {
+                            item_area
+                        };
+                        let mut element = NodeCache::new(element);
+                        element
+                            .layout(item_area, None, None, visible, direction, cells, state, ctx);
+                        Some(element)
+                    })
+                    .collect();
+            }
+            NodeValue::Group(elements) => {
+                elements
+                    .iter_mut()
+                    .zip(allocated)
+                    .for_each(|(el, allocation)| {
+                        el.layout(
+                            allocation,
+                            contextual_x_align,
+                            contextual_y_align,
+                            visible,
+                            direction,
+                            cells,
+                            state,
+                            ctx,
+                        )
+                    });
+            }
+            NodeValue::Empty => (),
+        }
+    }
+}
+
+/// The index, offset & extent of the elements of a virtual sequence between `visible_start` & `visible_end`,
+/// plus `overscan` elements on either side, where offsets are relative to the start of the sequence
+fn visible_items<S: Scalar>(
+    count: usize,
+    overscan: usize,
+    visible_start: S,
+    visible_end: S,
+    mut extent: impl FnMut(usize) -> S,
+) -> Vec<(usize, S, S)> {
+    let mut before = VecDeque::new();
+    let mut items = Vec::new();
+    let mut after = 0;
+    let mut offset = S::ZERO;
+    for i in 0..count {
+        let item = (i, offset, extent(i).max(S::ZERO));
+        offset = offset + item.2;
+        if offset <= visible_start {
+            before.push_back(item);
+            if before.len() > overscan {
+                before.pop_front();
             }
-            NodeValue::Group(_) | NodeValue::Empty => unreachable!(),
+        } else if item.1 < visible_end {
+            items.push(item);
+        } else if after < overscan {
+            items.push(item);
+            after += 1;
+        } else {
+            break;
         }
     }
+    before.into_iter().chain(items).collect()
 }
 
-impl Area {
+impl<S: Scalar> Area<S> {
     fn constrained(
         self,
-        constraints: &SizeConstraints,
+        constraints: &SizeConstraints<S>,
         contextual_x_align: XAlign,
         contextual_y_align: YAlign,
     ) -> Self {
@@ -399,15 +1281,16 @@ impl Area {
             width = (height * aspect).min(width);
             height = (width / aspect).min(height);
         }
+        let half = S::from_f64(0.5);
         let x = match constraints.x_align.unwrap_or(contextual_x_align) {
             XAlign::Leading => self.x,
             XAlign::Trailing => self.x + (self.width - width),
-            XAlign::Center => self.x + (self.width * 0.5) - (width * 0.5),
+            XAlign::Center => self.x + (self.width * half) - (width * half),
         };
         let y = match constraints.y_align.unwrap_or(contextual_y_align) {
             YAlign::Top => self.y,
             YAlign::Bottom => self.y + (self.height - height),
-            YAlign::Center => self.y + (self.height * 0.5) - (height * 0.5),
+            YAlign::Center => self.y + (self.height * half) - (height * half),
         };
         Area {
             x,
@@ -418,206 +1301,670 @@ impl Area {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Hash)]
 pub(crate) enum Orientation {
     Horizontal,
     Vertical,
 }
 
 #[allow(clippy::too_many_arguments)]
-pub(crate) fn layout_axis<State, Ctx>(
-    elements: &mut [NodeCache<State, Ctx>],
-    spacing: &f32,
-    available_area: Area,
+pub(crate) fn layout_axis<State, Ctx, S: Scalar>(
+    elements: &mut [NodeCache<State, Ctx, S>],
+    spacing: &S,
+    available_area: Area<S>,
     orientation: Orientation,
     x_align: XAlign,
     y_align: YAlign,
+    distribution: Option<Distribution>,
+    cells: bool,
     state: &mut State,
     ctx: &mut Ctx,
-    check: bool,
-) -> Vec<Area> {
-    let sizes: Vec<SizeConstraints> = elements
+) -> Vec<Area<S>> {
+    let sizes: Vec<SizeConstraints<S>> = elements
         .iter_mut()
-        .map(|element| element.constraints(available_area, state, ctx))
+        .map(|element| element.constraints(available_area, cells, state, ctx))
         .collect();
-    let element_count = elements.len();
+    axis_areas(
+        &sizes,
+        *spacing,
+        available_area,
+        orientation,
+        x_align,
+        y_align,
+        distribution,
+        cells,
+    )
+}
+
+/// Places items with the constraints `sizes` one after another along `orientation`, as a row or column does.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn axis_areas<S: Scalar>(
+    sizes: &[SizeConstraints<S>],
+    spacing: S,
+    available_area: Area<S>,
+    orientation: Orientation,
+    x_align: XAlign,
+    y_align: YAlign,
+    distribution: Option<Distribution>,
+    cells: bool,
+) -> Vec<Area<S>> {
+    let element_count = sizes.len();
+    let half = S::from_f64(0.5);
 
-    let total_spacing = *spacing * (element_count as i32 - 1).max(0) as f32;
+    let total_spacing = spacing * S::from_f64(element_count.saturating_sub(1) as f64);
     let available_size = match orientation {
         Orientation::Horizontal => available_area.width,
         Orientation::Vertical => available_area.height,
     } - total_spacing;
 
-    let default_size = available_size / element_count as f32;
-
-    let mut pool = 0.;
-    let mut final_sizes = vec![None; element_count];
-    let mut room_to_grow = vec![0.0; element_count];
-    let mut room_to_shrink = vec![0.0; element_count];
-
-    for (i, size_constraint) in sizes.iter().enumerate() {
-        let constraint = match orientation {
-            Orientation::Horizontal => size_constraint.width,
-            Orientation::Vertical => size_constraint.height,
+    let bounds: Vec<(Option<S>, Option<S>)> = sizes
+        .iter()
+        .map(|size_constraint| {
+            let constraint = match orientation {
+                Orientation::Horizontal => size_constraint.width,
+                Orientation::Vertical => size_constraint.height,
+            };
+            if let Some(aspect) = size_constraint.aspect {
+                let value = match orientation {
+                    Orientation::Horizontal => {
+                        size_constraint.height.clamping(available_area.height) * aspect
+                    }
+                    Orientation::Vertical => {
+                        size_constraint.width.clamping(available_area.width) / aspect
+                    }
+                };
+                (Some(value), Some(value))
+            } else {
+                (constraint.get_lower(), constraint.get_upper())
+            }
+        })
+        .collect();
+    let weights: Vec<S> = sizes
+        .iter()
+        .map(|size_constraint| size_constraint.weight.unwrap_or(S::ONE))
+        .collect();
+    let (final_sizes, pool) = distribute(available_size, &bounds, &weights);
+    let (final_sizes, pool) = if cells {
+        in_cells(final_sizes, pool)
+    } else {
+        (final_sizes, pool)
+    };
+    let whole = |value: S| if cells { value.floor() } else { value };
+    let mut current_pos = match orientation {
+        Orientation::Horizontal => match x_align {
+            XAlign::Leading => available_area.x,
+            XAlign::Center => available_area.x + (pool * half),
+            XAlign::Trailing => available_area.x + pool,
+        },
+        Orientation::Vertical => match y_align {
+            YAlign::Top => available_area.y,
+            YAlign::Center => available_area.y + (pool * half),
+            YAlign::Bottom => available_area.y + pool,
+        },
+    };
+    let mut gap = S::ZERO;
+    if let Some(distribution) = distribution.filter(|_| pool > S::ZERO) {
+        let start = match orientation {
+            Orientation::Horizontal => available_area.x,
+            Orientation::Vertical => available_area.y,
         };
-        let mut final_size = Option::<f32>::None;
-        let mut lower = constraint.get_lower();
-        let mut upper = constraint.get_upper();
-
-        if let Some(aspect) = size_constraint.aspect {
-            match orientation {
-                Orientation::Horizontal => {
-                    let value = sizes[i].height.clamping(available_area.height) * aspect;
-                    lower = Some(value);
-                    upper = Some(value);
-                }
-                Orientation::Vertical => {
-                    let value = sizes[i].width.clamping(available_area.width) / aspect;
-                    lower = Some(value);
-                    upper = Some(value);
-                }
+        let count = S::from_f64(element_count as f64);
+        match distribution {
+            // A single element has nothing to be placed between, so it falls back to alignment
+            Distribution::SpaceBetween if element_count > 1 => {
+                gap = whole(pool / (count - S::ONE));
+                current_pos = start;
+            }
+            Distribution::SpaceBetween => (),
+            Distribution::SpaceAround => {
+                gap = whole(pool / count);
+                current_pos = start + gap * half;
+            }
+            Distribution::SpaceEvenly => {
+                gap = whole(pool / (count + S::ONE));
+                current_pos = start + gap;
             }
         }
+    }
+
+    let mut areas = Vec::<Area<S>>::new();
+    for (&child_size, size) in final_sizes.iter().zip(sizes) {
+        let area = match orientation {
+            Orientation::Horizontal => Area {
+                x: current_pos,
+                y: available_area.y,
+                width: child_size,
+                height: available_area.height,
+            },
+            Orientation::Vertical => Area {
+                x: available_area.x,
+                y: current_pos,
+                width: available_area.width,
+                height: child_size,
+            },
+        }
+        .constrained(size, x_align, y_align);
+
+        areas.push(area);
+
+        current_pos = current_pos + child_size + spacing + gap;
+    }
+    areas
+}
+
+/// Distributes `available_size` between items bounded by `bounds`.
+///
+/// Every item starts with a share of the available size proportional to its weight,
+/// then space is moved between items until each one adheres to its bounds.
+///
+/// Returns the final size of each item & the leftover space (negative when items overflow)
+pub(crate) fn distribute<S: Scalar>(
+    available_size: S,
+    bounds: &[(Option<S>, Option<S>)],
+    weights: &[S],
+) -> (Vec<S>, S) {
+    let element_count = bounds.len();
+    let sum = |values: &mut dyn Iterator<Item = S>| values.fold(S::ZERO, |a, b| a + b);
+    let total_weight = sum(&mut weights.iter().copied());
+
+    let mut pool = S::ZERO;
+    let mut final_sizes = vec![S::ZERO; element_count];
+    let mut room_to_grow = vec![S::ZERO; element_count];
+    let mut room_to_shrink = vec![S::ZERO; element_count];
+
+    for (i, &(lower, upper)) in bounds.iter().enumerate() {
+        let default_size = if total_weight > S::ZERO {
+            available_size * weights[i] / total_weight
+        } else {
+            S::ZERO
+        };
+        let mut final_size = Option::<S>::None;
 
         if let Some(lower) = lower {
             if default_size < lower {
-                pool += default_size - lower;
+                pool = pool + default_size - lower;
                 final_size = Some(lower);
             }
         }
         if let Some(upper) = upper {
             if default_size > upper {
-                pool += default_size - upper;
+                pool = pool + default_size - upper;
                 final_size = Some(upper);
             }
         }
 
         if let Some(lower) = lower {
             if default_size >= lower {
-                room_to_shrink[i] = -(final_size.unwrap_or(default_size) - lower);
+                room_to_shrink[i] = lower - final_size.unwrap_or(default_size);
             }
         } else {
             // Effectively, this means the element can shrink to 0
-            room_to_shrink[i] = -default_size;
+            room_to_shrink[i] = S::ZERO - default_size;
         }
 
         if let Some(upper) = upper {
             if default_size <= upper {
-                room_to_grow[i] = -(final_size.unwrap_or(default_size) - upper);
+                room_to_grow[i] = upper - final_size.unwrap_or(default_size);
             }
         } else {
             // Effectively, this means the element can expand any amount
-            room_to_grow[i] = default_size * 10.;
+            room_to_grow[i] = default_size * S::from_f64(10.);
         }
 
-        final_sizes[i] = final_size.unwrap_or(default_size).into();
+        final_sizes[i] = final_size.unwrap_or(default_size);
     }
 
-    fn can_accommodate(room: &[f32]) -> bool {
-        room.iter().filter(|r| r.abs() > 0.).count() as f32 > 0.
-    }
+    // Items with no weight never take part in redistribution
+    let candidates = |room: &[S], grow: bool| -> Vec<usize> {
+        room.iter()
+            .enumerate()
+            .filter(|(i, &r)| weights[*i] > S::ZERO && if grow { r > S::ZERO } else { r < S::ZERO })
+            .map(|(i, _)| i)
+            .collect()
+    };
 
     let limit = 5;
     let mut i = 0;
+    let epsilon = S::from_f64(0.1);
     loop {
         if i > limit {
             break;
         }
         i += 1;
-        let pool_empty = pool.abs() < 0.1;
-        if !pool_empty && pool.is_sign_positive() && can_accommodate(&room_to_grow) {
+        let pool_empty = pool.abs() < epsilon;
+        let growable = candidates(&room_to_grow, true);
+        let shrinkable = candidates(&room_to_shrink, false);
+        if !pool_empty && pool > S::ZERO && !growable.is_empty() {
             // We need to use more room
-            let mut enumerated_room: Vec<(usize, f32)> = room_to_grow
+            let candidate_weight = sum(&mut growable.iter().map(|&i| weights[i]));
+            let smallest_room = growable
                 .iter()
-                .enumerate()
-                .map(|(i, v)| (i, *v))
-                .filter(|(_, v)| *v != 0.)
-                .collect();
-            enumerated_room.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
-            let distribution_candidates = room_to_grow
-                .iter()
-                .filter(|r| r.abs() > 0. && r.is_sign_positive())
-                .count() as f32;
-            let distribution_amount =
-                (pool / distribution_candidates).min(enumerated_room.first().unwrap().1);
-            pool -= distribution_amount * distribution_candidates;
-            enumerated_room.iter().for_each(|&(i, _)| {
-                if room_to_grow[i].abs() > 0. && room_to_grow[i].is_sign_positive() {
-                    room_to_grow[i] -= distribution_amount;
-                    if let Some(size) = &mut final_sizes[i] {
-                        *size += distribution_amount
-                    }
-                }
+                .map(|&i| room_to_grow[i] / weights[i])
+                .reduce(S::min)
+                .unwrap_or(pool);
+            let distribution_amount = (pool / candidate_weight).min(smallest_room);
+            pool = pool - distribution_amount * candidate_weight;
+            growable.iter().for_each(|&i| {
+                room_to_grow[i] = room_to_grow[i] - distribution_amount * weights[i];
+                final_sizes[i] = final_sizes[i] + distribution_amount * weights[i];
             });
-        } else if !pool_empty && pool.is_sign_negative() && can_accommodate(&room_to_shrink) {
+        } else if !pool_empty && pool < S::ZERO && !shrinkable.is_empty() {
             // We need to use less room
-            let mut enumerated_room: Vec<(usize, f32)> = room_to_shrink
-                .iter()
-                .enumerate()
-                .map(|(i, v)| (i, *v))
-                .filter(|(_, v)| *v != 0.)
-                .collect();
-            enumerated_room.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap().reverse());
-            let distribution_candidates = room_to_shrink
+            let candidate_weight = sum(&mut shrinkable.iter().map(|&i| weights[i]));
+            let smallest_room = shrinkable
                 .iter()
-                .filter(|r| r.abs() > 0. && r.is_sign_negative())
-                .count() as f32;
-            let distribution_amount =
-                (pool / distribution_candidates).max(enumerated_room.first().unwrap().1);
-            pool -= distribution_amount * distribution_candidates;
-            enumerated_room.iter().for_each(|&(i, _)| {
-                if room_to_shrink[i].abs() > 0. && room_to_shrink[i].is_sign_negative() {
-                    room_to_shrink[i] -= distribution_amount;
-                    if let Some(size) = &mut final_sizes[i] {
-                        *size += distribution_amount
-                    }
-                }
+                .map(|&i| room_to_shrink[i] / weights[i])
+                .reduce(S::max)
+                .unwrap_or(pool);
+            let distribution_amount = (pool / candidate_weight).max(smallest_room);
+            pool = pool - distribution_amount * candidate_weight;
+            shrinkable.iter().for_each(|&i| {
+                room_to_shrink[i] = room_to_shrink[i] - distribution_amount * weights[i];
+                final_sizes[i] = final_sizes[i] + distribution_amount * weights[i];
             });
         } else {
             break;
         }
     }
+    (final_sizes, pool)
+}
 
-    let mut current_pos = match orientation {
-        Orientation::Horizontal => match x_align {
-            XAlign::Leading => available_area.x,
-            XAlign::Center => available_area.x + (pool * 0.5),
-            XAlign::Trailing => available_area.x + pool,
-        },
-        Orientation::Vertical => match y_align {
-            YAlign::Top => available_area.y,
-            YAlign::Center => available_area.y + (pool * 0.5),
-            YAlign::Bottom => available_area.y + pool,
-        },
+/// Rounds sizes from [`distribute`] to whole cells, keeping their total.
+///
+/// Cells lost to rounding are handed out one at a time, to the items with the largest remainders first,
+/// & in order for items with equal remainders.
+pub(crate) fn in_cells<S: Scalar>(sizes: Vec<S>, pool: S) -> (Vec<S>, S) {
+    let sum = |values: &[S]| values.iter().fold(S::ZERO, |a, &b| a + b);
+    let total = sum(&sizes);
+    // Rounding up can't use more than the available cells, unless the items already overflow
+    let available = total + pool;
+    let target = if pool >= S::ZERO {
+        total.round().min((available + S::from_f64(0.001)).floor())
+    } else {
+        total.round()
+    };
+    let mut cells: Vec<S> = sizes.iter().map(|size| size.floor()).collect();
+    let leftover = (target - sum(&cells)).max(S::ZERO).to_f64() as usize;
+    // Remainders are compared coarsely, so items sized by the same calculation are treated as equal
+    let remainder = |i: usize| {
+        ((sizes[i] - cells[i]) * S::from_f64(1000.))
+            .round()
+            .to_f64() as i32
     };
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by_key(|&i| core::cmp::Reverse(remainder(i)));
+    order
+        .into_iter()
+        .take(leftover)
+        .for_each(|i| cells[i] = cells[i] + S::ONE);
+    let pool = available - sum(&cells);
+    (cells, pool)
+}
 
-    let mut areas = Vec::<Area>::new();
-    for (i, child) in elements.iter_mut().enumerate() {
-        let child_size = final_sizes[i].unwrap();
+pub(crate) struct WrapLines<S> {
+    pub(crate) sizes: Vec<SizeConstraints<S>>,
+    /// The range of elements on each line
+    pub(crate) lines: Vec<core::ops::Range<usize>>,
+    /// The constraint of each line along the cross axis
+    pub(crate) line_constraints: Vec<Constraint<S>>,
+}
 
-        let area = match orientation {
-            Orientation::Horizontal => Area {
-                x: current_pos,
-                y: available_area.y,
-                width: child_size,
-                height: available_area.height,
-            },
-            Orientation::Vertical => Area {
-                x: available_area.x,
-                y: current_pos,
-                width: available_area.width,
-                height: child_size,
-            },
+impl Orientation {
+    pub(crate) fn main<S: Scalar>(&self, size: &SizeConstraints<S>) -> Constraint<S> {
+        match self {
+            Orientation::Horizontal => size.width,
+            Orientation::Vertical => size.height,
+        }
+    }
+    pub(crate) fn cross<S: Scalar>(&self, size: &SizeConstraints<S>) -> Constraint<S> {
+        match self {
+            Orientation::Horizontal => size.height,
+            Orientation::Vertical => size.width,
+        }
+    }
+}
+
+/// Breaks elements into lines, moving to a new line when the next element's
+/// smallest size along the main axis no longer fits
+pub(crate) fn wrap_lines<State, Ctx, S: Scalar>(
+    elements: &mut [NodeCache<State, Ctx, S>],
+    orientation: Orientation,
+    spacing: S,
+    available_area: Area<S>,
+    cells: bool,
+    state: &mut State,
+    ctx: &mut Ctx,
+) -> WrapLines<S> {
+    let sizes: Vec<SizeConstraints<S>> = elements
+        .iter_mut()
+        .map(|element| element.constraints(available_area, cells, state, ctx))
+        .collect();
+    let available_size = match orientation {
+        Orientation::Horizontal => available_area.width,
+        Orientation::Vertical => available_area.height,
+    };
+    let mut lines: Vec<core::ops::Range<usize>> = Vec::new();
+    let mut line_length = S::ZERO;
+    for (i, size) in sizes.iter().enumerate() {
+        let main = orientation.main(size);
+        // Elements with no bounds along the main axis take up a whole line
+        let basis = main
+            .get_lower()
+            .or(main.get_upper())
+            .unwrap_or(available_size);
+        match lines.last_mut() {
+            Some(line) if line_length + spacing + basis <= available_size => {
+                line.end = i + 1;
+                line_length = line_length + spacing + basis;
+            }
+            _ => {
+                lines.push(i..i + 1);
+                line_length = basis;
+            }
         }
-        .constrained(&sizes[i], x_align, y_align);
+    }
+    let line_constraints = lines
+        .iter()
+        .map(|line| {
+            sizes[line.clone()]
+                .iter()
+                .map(|size| orientation.cross(size))
+                .reduce(|current, next| current.combine_adjacent_priority(next))
+                .unwrap_or(Constraint::none())
+        })
+        .collect();
+    WrapLines {
+        sizes,
+        lines,
+        line_constraints,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn layout_wrap<State, Ctx, S: Scalar>(
+    elements: &mut [NodeCache<State, Ctx, S>],
+    orientation: Orientation,
+    spacing: (S, S),
+    available_area: Area<S>,
+    x_align: XAlign,
+    y_align: YAlign,
+    cells: bool,
+    state: &mut State,
+    ctx: &mut Ctx,
+) -> Vec<Area<S>> {
+    let (spacing, line_spacing) = spacing;
+    let wrapped = wrap_lines(
+        elements,
+        orientation,
+        spacing,
+        available_area,
+        cells,
+        state,
+        ctx,
+    );
+    let (available_main, available_cross, main_align, cross_align) = match orientation {
+        Orientation::Horizontal => (
+            available_area.width,
+            available_area.height,
+            x_align.fraction(),
+            y_align.fraction(),
+        ),
+        Orientation::Vertical => (
+            available_area.height,
+            available_area.width,
+            y_align.fraction(),
+            x_align.fraction(),
+        ),
+    };
+    let bounds = |constraints: &mut dyn Iterator<Item = Constraint<S>>| {
+        constraints
+            .map(|constraint| (constraint.get_lower(), constraint.get_upper()))
+            .collect::<Vec<_>>()
+    };
 
-        if !check {
-            child.layout(area, Some(x_align), Some(y_align), state, ctx);
+    let line_count = wrapped.lines.len();
+    let cells_if_needed = |(sizes, pool)| {
+        if cells {
+            in_cells(sizes, pool)
         } else {
-            areas.push(area);
+            (sizes, pool)
         }
+    };
+    let gaps = |count: usize| S::from_f64(count.saturating_sub(1) as f64);
+    let (line_sizes, line_pool) = cells_if_needed(distribute(
+        available_cross - line_spacing * gaps(line_count),
+        &bounds(&mut wrapped.line_constraints.iter().copied()),
+        &vec![S::ONE; line_count],
+    ));
 
-        current_pos += child_size + *spacing;
+    let mut areas = Vec::with_capacity(elements.len());
+    let mut cross_pos = line_pool * cross_align;
+    for (line, line_size) in wrapped.lines.iter().zip(line_sizes) {
+        let count = line.len();
+        let (sizes, pool) = cells_if_needed(distribute(
+            available_main - spacing * gaps(count),
+            &bounds(
+                &mut wrapped.sizes[line.clone()]
+                    .iter()
+                    .map(|size| orientation.main(size)),
+            ),
+            &vec![S::ONE; count],
+        ));
+        let mut main_pos = pool * main_align;
+        for (i, size) in line.clone().zip(sizes) {
+            let area = match orientation {
+                Orientation::Horizontal => Area {
+                    x: available_area.x + main_pos,
+                    y: available_area.y + cross_pos,
+                    width: size,
+                    height: line_size,
+                },
+                Orientation::Vertical => Area {
+                    x: available_area.x + cross_pos,
+                    y: available_area.y + main_pos,
+                    width: line_size,
+                    height: size,
+                },
+            };
+            areas.push(area.constrained(&wrapped.sizes[i], x_align, y_align));
+            main_pos = main_pos + size + spacing;
+        }
+        cross_pos = cross_pos + line_size + line_spacing;
     }
     areas
 }
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct GridPlacement {
+    pub(crate) column: usize,
+    pub(crate) row: usize,
+    pub(crate) column_span: usize,
+    pub(crate) row_span: usize,
+}
+
+pub(crate) struct GridTracks<S> {
+    pub(crate) placements: Vec<GridPlacement>,
+    pub(crate) sizes: Vec<SizeConstraints<S>>,
+    /// The resolved constraint & distribution weight of each column
+    pub(crate) columns: Vec<(Constraint<S>, S)>,
+    /// The resolved constraint & distribution weight of each row
+    pub(crate) rows: Vec<(Constraint<S>, S)>,
+}
+
+/// Places cells left to right, top to bottom, into the first free run of cells that fits each span
+pub(crate) fn grid_placements(spans: &[(usize, usize)], column_count: usize) -> Vec<GridPlacement> {
+    let column_count = column_count.max(1);
+    let mut occupied: Vec<Vec<bool>> = Vec::new();
+    let mut cursor = 0;
+    spans
+        .iter()
+        .map(|&(column_span, row_span)| {
+            let column_span = column_span.clamp(1, column_count);
+            let row_span = row_span.max(1);
+            let fits = |occupied: &Vec<Vec<bool>>, row: usize, column: usize| {
+                column + column_span <= column_count
+                    && (row..row + row_span).all(|r| {
+                        (column..column + column_span)
+                            .all(|c| occupied.get(r).map(|cells| !cells[c]).unwrap_or(true))
+                    })
+            };
+            while !fits(&occupied, cursor / column_count, cursor % column_count) {
+                cursor += 1;
+            }
+            let (row, column) = (cursor / column_count, cursor % column_count);
+            for r in row..row + row_span {
+                if occupied.len() <= r {
+                    occupied.resize(r + 1, vec![false; column_count]);
+                }
+                (column..column + column_span).for_each(|c| occupied[r][c] = true);
+            }
+            GridPlacement {
+                column,
+                row,
+                column_span,
+                row_span,
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn grid_tracks<State, Ctx, S: Scalar>(
+    elements: &mut [NodeCache<State, Ctx, S>],
+    columns: &[Track<S>],
+    rows: &[Track<S>],
+    available_area: Area<S>,
+    cells: bool,
+    state: &mut State,
+    ctx: &mut Ctx,
+) -> GridTracks<S> {
+    let sizes: Vec<SizeConstraints<S>> = elements
+        .iter_mut()
+        .map(|element| element.constraints(available_area, cells, state, ctx))
+        .collect();
+    let spans: Vec<(usize, usize)> = sizes
+        .iter()
+        .map(|size| (size.column_span.unwrap_or(1), size.row_span.unwrap_or(1)))
+        .collect();
+    let placements = grid_placements(&spans, columns.len());
+    let column_count = columns.len().max(1);
+    let row_count = placements
+        .iter()
+        .map(|p| p.row + p.row_span)
+        .max()
+        .unwrap_or(0)
+        .max(rows.len());
+
+    // Tracks beyond the defined ones size like `Track::Fraction(1.)`
+    let resolve = |tracks: &[Track<S>], count: usize, vertical: bool| -> Vec<(Constraint<S>, S)> {
+        (0..count)
+            .map(|index| {
+                // Only cells contained by a single track contribute to its size
+                let content = placements
+                    .iter()
+                    .zip(sizes.iter())
+                    .filter(|(placement, _)| {
+                        if vertical {
+                            placement.row == index && placement.row_span == 1
+                        } else {
+                            placement.column == index && placement.column_span == 1
+                        }
+                    })
+                    .map(|(_, size)| if vertical { size.height } else { size.width })
+                    .reduce(|current, next| current.combine_adjacent_priority(next))
+                    .unwrap_or(Constraint::none());
+                match tracks
+                    .get(index)
+                    .copied()
+                    .unwrap_or(Track::Fraction(S::ONE))
+                {
+                    Track::Fixed(size) => (Constraint::new(Some(size), Some(size)), S::ONE),
+                    Track::Range { min, max } => (
+                        Constraint::new(min, max).combine_explicit_with_child(content),
+                        S::ONE,
+                    ),
+                    Track::Fraction(fraction) => (content, fraction.max(S::ZERO)),
+                }
+            })
+            .collect()
+    };
+    GridTracks {
+        columns: resolve(columns, column_count, false),
+        rows: resolve(rows, row_count, true),
+        placements,
+        sizes,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn layout_grid<State, Ctx, S: Scalar>(
+    elements: &mut [NodeCache<State, Ctx, S>],
+    columns: &[Track<S>],
+    rows: &[Track<S>],
+    spacing: (S, S),
+    available_area: Area<S>,
+    x_align: XAlign,
+    y_align: YAlign,
+    cells: bool,
+    state: &mut State,
+    ctx: &mut Ctx,
+) -> Vec<Area<S>> {
+    let tracks = grid_tracks(elements, columns, rows, available_area, cells, state, ctx);
+
+    let offsets = |tracks: &[(Constraint<S>, S)], available: S, spacing: S, align: S| {
+        let total_spacing = spacing * S::from_f64(tracks.len().saturating_sub(1) as f64);
+        let bounds: Vec<(Option<S>, Option<S>)> = tracks
+            .iter()
+            .map(|(constraint, _)| (constraint.get_lower(), constraint.get_upper()))
+            .collect();
+        let weights: Vec<S> = tracks.iter().map(|(_, weight)| *weight).collect();
+        let (sizes, pool) = distribute(available - total_spacing, &bounds, &weights);
+        let (sizes, pool) = if cells {
+            in_cells(sizes, pool)
+        } else {
+            (sizes, pool)
+        };
+        let mut current = pool * align;
+        sizes
+            .into_iter()
+            .map(|size| {
+                let start = current;
+                current = current + size + spacing;
+                (start, size)
+            })
+            .collect::<Vec<(S, S)>>()
+    };
+    let column_offsets = offsets(
+        &tracks.columns,
+        available_area.width,
+        spacing.0,
+        x_align.fraction(),
+    );
+    let row_offsets = offsets(
+        &tracks.rows,
+        available_area.height,
+        spacing.1,
+        y_align.fraction(),
+    );
+
+    tracks
+        .placements
+        .iter()
+        .zip(tracks.sizes.iter())
+        .map(|(placement, size)| {
+            let span = |offsets: &[(S, S)], start: usize, count: usize| {
+                let (first, _) = offsets[start];
+                let (last, last_size) = offsets[start + count - 1];
+                (first, last + last_size - first)
+            };
+            let (x, width) = span(&column_offsets, placement.column, placement.column_span);
+            let (y, height) = span(&row_offsets, placement.row, placement.row_span);
+            Area {
+                x: available_area.x + x,
+                y: available_area.y + y,
+                width,
+                height,
+            }
+            .constrained(size, x_align, y_align)
+        })
+        .collect()
+}
@@ -1,3 +1,4 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![warn(missing_docs)]
 
 /*!
@@ -11,18 +12,37 @@ _This library **only** implements layout & could be integrated with a range of U
 See [`Layout`] for setup.
 
 See [`Node`] for layout customization.
+
+# Cargo Features
+- `sync`: Makes layouts `Send + Sync`, so they can be computed on one thread & drawn on another.
+  Closures passed to backer must then be `Send + Sync` too (see [`traits::MaybeSync`]). Enables `std`.
+- `std` (default): Uses the standard library. Without it backer is `no_std`, & only needs `alloc`,
+  so it can lay out small displays on microcontrollers.
 */
 
+extern crate alloc;
+
+mod animation;
+mod cell_buffer;
+mod computed;
 mod constraints;
 mod debug;
 mod drawable;
+mod export;
 mod layout;
+#[cfg(not(feature = "std"))]
+mod math;
 mod modifiers;
 mod node;
 mod node_cache;
+mod prelude;
+mod retained;
+mod shared;
 mod subtree;
 mod tests;
+mod transition;
 
+pub use layout::ComputedLayout;
 pub use layout::Layout;
 pub use node::Node;
 pub use node::NodeWith;
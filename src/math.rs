@@ -0,0 +1,139 @@
+//! Float functions that `core` doesn't provide, for builds without the `std` feature.
+//!
+//! Everything is calculated in `f64`, which is accurate enough for layout & easing in `f32`.
+//! `floor` & `round` are provided by [`crate::traits::Scalar`] instead, which has them for every scalar.
+
+use crate::traits::Scalar;
+use core::f64::consts::{LN_2, PI, TAU};
+
+/// The float methods backer uses that are only inherent with `std`, other than those of [`Scalar`]
+pub(crate) trait Float: Scalar {
+    fn powi(self, n: i32) -> Self;
+    fn exp(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+}
+
+impl Float for f32 {
+    fn powi(self, n: i32) -> Self {
+        powi(self as f64, n) as f32
+    }
+    fn exp(self) -> Self {
+        exp(self as f64) as f32
+    }
+    fn sqrt(self) -> Self {
+        sqrt(self as f64) as f32
+    }
+    fn sin(self) -> Self {
+        sin(self as f64) as f32
+    }
+    fn cos(self) -> Self {
+        sin(self as f64 + PI / 2.) as f32
+    }
+}
+
+/// `value` without its fractional part
+fn trunc(value: f64) -> f64 {
+    // Larger values have no fractional part, & NaN & infinities are returned as they are
+    if value.is_nan() || value.abs() >= 4_503_599_627_370_496. {
+        return value;
+    }
+    let truncated = value as i64 as f64;
+    // Keeps the sign of values between -1 & 0
+    if truncated == 0. {
+        0. * value
+    } else {
+        truncated
+    }
+}
+
+pub(crate) fn floor(value: f64) -> f64 {
+    let truncated = trunc(value);
+    if truncated > value {
+        truncated - 1.
+    } else {
+        truncated
+    }
+}
+
+/// Rounds halves away from zero, like `f64::round`
+pub(crate) fn round(value: f64) -> f64 {
+    let truncated = trunc(value);
+    if (value - truncated).abs() >= 0.5 {
+        truncated + value.signum()
+    } else {
+        truncated
+    }
+}
+
+fn powi(mut base: f64, n: i32) -> f64 {
+    let mut exponent = n.unsigned_abs();
+    let mut result = 1.;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result *= base;
+        }
+        base *= base;
+        exponent >>= 1;
+    }
+    if n < 0 {
+        1. / result
+    } else {
+        result
+    }
+}
+
+fn exp(value: f64) -> f64 {
+    if value.is_nan() {
+        return value;
+    }
+    if value > 709. {
+        return f64::INFINITY;
+    }
+    if value < -745. {
+        return 0.;
+    }
+    // e^value = 2^k * e^r, where r is small enough for the series to converge quickly
+    let k = round(value / LN_2);
+    let r = value - k * LN_2;
+    let mut term = 1.;
+    let mut sum = 1.;
+    for i in 1..20 {
+        term *= r / i as f64;
+        sum += term;
+    }
+    // Split so neither power of two leaves the range of normal floats
+    let half = (k / 2.) as i32;
+    sum * powi(2., half) * powi(2., k as i32 - half)
+}
+
+fn sqrt(value: f64) -> f64 {
+    if value.is_nan() || value < 0. {
+        return f64::NAN;
+    }
+    if value == 0. || value == f64::INFINITY {
+        return value;
+    }
+    // Halving the exponent gives a first guess within a factor of 2, which Newton's method refines
+    let mut root = f64::from_bits((value.to_bits() >> 1) + (1023 << 51));
+    for _ in 0..8 {
+        root = 0.5 * (root + value / root);
+    }
+    root
+}
+
+fn sin(value: f64) -> f64 {
+    if !value.is_finite() {
+        return f64::NAN;
+    }
+    // Reduced to between -π & π, where the series is accurate
+    let x = value - round(value / TAU) * TAU;
+    let mut term = x;
+    let mut sum = x;
+    for i in 1..14 {
+        term *= -x * x / ((2 * i) * (2 * i + 1)) as f64;
+        sum += term;
+    }
+    sum
+}
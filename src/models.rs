@@ -1,4 +1,14 @@
-use std::rc::Rc;
+pub use crate::cell_buffer::CellBuffer;
+use crate::prelude::*;
+use crate::shared::shared_fn;
+use crate::traits::Scalar;
+use core::{
+    hash::{Hash, Hasher},
+    mem::discriminant,
+    ops::{Bound, RangeBounds},
+    ptr::fn_addr_eq,
+    time::Duration,
+};
 
 /// An alignment along the X and/or Y axis
 #[derive(Debug, Clone, Copy)]
@@ -37,20 +47,333 @@ pub enum Align {
     CenterCenter,
 }
 
+/// A strategy for distributing leftover space between the elements of a row or column
+#[derive(Debug, Clone, Copy, PartialEq, Hash)]
+pub enum Distribution {
+    /// Places leftover space evenly between elements, with no space before the first or after the last element
+    SpaceBetween,
+    /// Places leftover space evenly around each element, so the space before the first & after the last element
+    /// is half of the space between elements
+    SpaceAround,
+    /// Places leftover space evenly between elements & before the first & after the last element
+    SpaceEvenly,
+}
+
+/// The direction in which content is laid out horizontally.
+///
+/// In right to left layout, leading & trailing alignment & padding are mirrored,
+/// & rows place their first element on the right.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Hash)]
+pub enum Direction {
+    /// Leading is left & trailing is right - the default direction
+    #[default]
+    LeftToRight,
+    /// Leading is right & trailing is left
+    RightToLeft,
+}
+
+/// A size proposed to a [`crate::nodes::draw_measured`] node, to find out what size it would like to be.
+///
+/// An axis with a value asks what size the node would like to be if it was given that much space along the axis.
+/// `Some(0.)` asks for the node's smallest size along the axis.
+/// An axis left unspecified asks for the node's ideal size along the axis.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ProposedSize<S = f32> {
+    /// The proposed width, or `None` if the width is unspecified
+    pub width: Option<S>,
+    /// The proposed height, or `None` if the height is unspecified
+    pub height: Option<S>,
+}
+
+/// A deterministic [`crate::traits::TextMeasurer`] where every character has the same width,
+/// for unit tests & terminal UIs.
+///
+/// Text is wrapped at spaces. Words that are wider than the available width are placed on their own line.
 #[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Monospace {
+    /// The width of every character
+    pub char_width: f32,
+    /// The height of every line
+    pub line_height: f32,
+}
+
+impl Default for Monospace {
+    /// Measures text in character cells, for terminal UIs
+    fn default() -> Self {
+        Self::new(1., 1.)
+    }
+}
+
+impl Monospace {
+    /// Creates a new [`Monospace`] text measurer
+    pub fn new(char_width: f32, line_height: f32) -> Self {
+        Self {
+            char_width,
+            line_height,
+        }
+    }
+    /// Breaks `text` into lines no wider than `max_width`, the same way it's measured
+    pub fn wrap(&self, text: &str, max_width: Option<f32>) -> Vec<String> {
+        let max_columns = max_width.map(|width| (width / self.char_width).floor().max(0.) as usize);
+        text.split('\n')
+            .flat_map(|paragraph| {
+                let Some(max_columns) = max_columns else {
+                    return vec![paragraph.to_string()];
+                };
+                let mut lines = vec![String::new()];
+                for word in paragraph.split(' ') {
+                    let line = lines.last_mut().expect("There is always a line");
+                    let columns = line.chars().count();
+                    if columns == 0 {
+                        line.push_str(word);
+                    } else if columns + 1 + word.chars().count() <= max_columns {
+                        line.push(' ');
+                        line.push_str(word);
+                    } else {
+                        lines.push(word.to_string());
+                    }
+                }
+                lines
+            })
+            .collect()
+    }
+}
+
+/// The axes along which a [`crate::nodes::scroll`] node's content can scroll
+#[derive(Debug, Clone, Copy, PartialEq, Hash)]
+pub enum ScrollAxis {
+    /// Content can be taller than the scroll node
+    Vertical,
+    /// Content can be wider than the scroll node
+    Horizontal,
+    /// Content can be both taller & wider than the scroll node
+    Both,
+}
+
+impl ScrollAxis {
+    pub(crate) fn horizontal(&self) -> bool {
+        matches!(self, ScrollAxis::Horizontal | ScrollAxis::Both)
+    }
+    pub(crate) fn vertical(&self) -> bool {
+        matches!(self, ScrollAxis::Vertical | ScrollAxis::Both)
+    }
+}
+
+/// The size of a [`crate::nodes::scroll`] node & its content, used to determine the scroll offset
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ScrollInfo<S = f32> {
+    /// The visible area of the scroll node
+    pub viewport: Area<S>,
+    /// The width of the scrolled content
+    pub content_width: S,
+    /// The height of the scrolled content
+    pub content_height: S,
+}
+
+impl<S: Scalar> ScrollInfo<S> {
+    /// The largest horizontal offset that keeps the content filling the viewport
+    pub fn max_offset_x(&self) -> S {
+        (self.content_width - self.viewport.width).max(S::ZERO)
+    }
+    /// The largest vertical offset that keeps the content filling the viewport
+    pub fn max_offset_y(&self) -> S {
+        (self.content_height - self.viewport.height).max(S::ZERO)
+    }
+}
+
+/// How an animation progresses over its duration
+/// (see [`crate::NodeWith::animate`] & [`crate::NodeWith::transition`])
+#[derive(Debug, Clone, Copy, Default)]
+pub enum Easing {
+    /// Progresses at a constant rate
+    #[default]
+    Linear,
+    /// Starts slowly & speeds up
+    EaseIn,
+    /// Starts quickly & slows down
+    EaseOut,
+    /// Starts & ends slowly
+    EaseInOut,
+    /// Moves like a spring which settles by the end of the duration.
+    ///
+    /// `damping` is the damping ratio, from 0 to 1. Lower values overshoot & bounce more,
+    /// while 1 settles as quickly as possible without overshooting.
+    Spring {
+        /// The damping ratio, from 0 to 1
+        damping: f32,
+    },
+    /// Follows a custom curve, which maps linear progress from 0 to 1 to eased progress.
+    ///
+    /// Curves should map 0 to 0 & 1 to 1, though they may leave that range in between.
+    Custom(fn(f32) -> f32),
+}
+
+impl PartialEq for Easing {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Easing::Spring { damping: a }, Easing::Spring { damping: b }) => a == b,
+            (Easing::Custom(a), Easing::Custom(b)) => fn_addr_eq(*a, *b),
+            (a, b) => discriminant(a) == discriminant(b),
+        }
+    }
+}
+
+impl Hash for Easing {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        discriminant(self).hash(state);
+        match self {
+            Easing::Spring { damping } => damping.to_bits().hash(state),
+            Easing::Custom(curve) => curve.hash(state),
+            Easing::Linear | Easing::EaseIn | Easing::EaseOut | Easing::EaseInOut => (),
+        }
+    }
+}
+
+impl Easing {
+    /// Maps linear progress from 0 to 1 to eased progress from 0 to 1
+    pub fn ease(&self, t: f32) -> f32 {
+        let t = t.clamp(0., 1.);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1. - (1. - t).powi(3),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    4. * t * t * t
+                } else {
+                    1. - (-2. * t + 2.).powi(3) / 2.
+                }
+            }
+            Easing::Spring { .. } if t >= 1. => 1.,
+            Easing::Spring { damping } => {
+                let damping = damping.clamp(0.05, 1.);
+                // The natural frequency is chosen so the oscillation has decayed to ~0.1% by the end
+                let decay = 6.9;
+                let frequency = decay / damping;
+                if damping >= 1. {
+                    1. - (-frequency * t).exp() * (1. + frequency * t)
+                } else {
+                    let damped = frequency * (1. - damping * damping).sqrt();
+                    1. - (-decay * t).exp()
+                        * ((damped * t).cos() + decay / damped * (damped * t).sin())
+                }
+            }
+            Easing::Custom(curve) => curve(t),
+        }
+    }
+}
+
+/// An enter & exit transition for a node, as it's added to & removed from the layout
+/// (see [`crate::NodeWith::transition`])
+///
+/// Entering nodes move from the transition's opacity, offset & scale to their laid out appearance,
+/// & leaving nodes move back.
+///
+/// ```rust
+/// use backer::models::*;
+/// use std::time::Duration;
+///
+/// let transition = Transition::new(Duration::from_millis(300), Easing::EaseOut)
+///     .fade()
+///     .slide(0., 20.);
+/// assert_eq!(transition.opacity, 0.);
+/// assert_eq!(transition.offset_y, 20.);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transition {
+    /// How long the transition takes
+    pub duration: Duration,
+    /// How the transition progresses over its duration
+    pub easing: Easing,
+    /// The opacity nodes enter from & leave to, which is passed to [`crate::nodes::draw_faded`] nodes
+    pub opacity: f32,
+    /// The horizontal offset nodes enter from & leave to
+    pub offset_x: f32,
+    /// The vertical offset nodes enter from & leave to
+    pub offset_y: f32,
+    /// The scale nodes enter from & leave to, around their center
+    pub scale: f32,
+}
+
+impl Transition {
+    /// Creates a transition that doesn't change the node's appearance, to be customized with
+    /// [`Transition::fade`], [`Transition::slide`] & [`Transition::scale`]
+    pub fn new(duration: Duration, easing: Easing) -> Self {
+        Self {
+            duration,
+            easing,
+            opacity: 1.,
+            offset_x: 0.,
+            offset_y: 0.,
+            scale: 1.,
+        }
+    }
+    /// Fades nodes in from & out to full transparency
+    pub fn fade(self) -> Self {
+        Self {
+            opacity: 0.,
+            ..self
+        }
+    }
+    /// Slides nodes in from & out to an offset
+    pub fn slide(self, x: f32, y: f32) -> Self {
+        Self {
+            offset_x: x,
+            offset_y: y,
+            ..self
+        }
+    }
+    /// Scales nodes in from & out to `scale` times their size
+    pub fn scale(self, scale: f32) -> Self {
+        Self { scale, ..self }
+    }
+}
+
+impl Hash for Transition {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self.duration, self.easing).hash(state);
+        [self.opacity, self.offset_x, self.offset_y, self.scale]
+            .iter()
+            .for_each(|value| value.to_bits().hash(state));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Hash)]
 pub(crate) enum XAlign {
     Leading,
     Center,
     Trailing,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Hash)]
 pub(crate) enum YAlign {
     Top,
     Center,
     Bottom,
 }
 
+impl XAlign {
+    /// The portion of leftover space placed before aligned content
+    pub(crate) fn fraction<S: Scalar>(&self) -> S {
+        match self {
+            XAlign::Leading => S::ZERO,
+            XAlign::Center => S::from_f64(0.5),
+            XAlign::Trailing => S::ONE,
+        }
+    }
+}
+
+impl YAlign {
+    /// The portion of leftover space placed before aligned content
+    pub(crate) fn fraction<S: Scalar>(&self) -> S {
+        match self {
+            YAlign::Top => S::ZERO,
+            YAlign::Center => S::from_f64(0.5),
+            YAlign::Bottom => S::ONE,
+        }
+    }
+}
+
 impl Align {
     pub(crate) fn axis_aligns(&self) -> (Option<XAlign>, Option<YAlign>) {
         let (x_align, y_align) = match self {
@@ -74,21 +397,66 @@ impl Align {
     }
 }
 
+/// A rectangle of character cells in a terminal (see [`Area::to_cells`])
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CellRect {
+    /// The left-most column
+    pub x: i32,
+    /// The top-most row
+    pub y: i32,
+    /// The number of columns
+    pub width: u32,
+    /// The number of rows
+    pub height: u32,
+}
+
+impl CellRect {
+    /// Creates a new [`CellRect`].
+    pub fn new(x: i32, y: i32, width: u32, height: u32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+}
+
+/// Rounds halves up, rather than away from zero, so areas on either side of zero round the same way
+fn round_edge<S: Scalar>(value: S) -> S {
+    (value + S::from_f64(0.5)).floor()
+}
+
+impl From<CellRect> for Area {
+    fn from(rect: CellRect) -> Self {
+        Area::new(
+            rect.x as f32,
+            rect.y as f32,
+            rect.width as f32,
+            rect.height as f32,
+        )
+    }
+}
+
 /// An allocation of screen space as a rectangle
+///
+/// Coordinates are `f32` by default, & can be any other [`Scalar`], like `f64` for very large canvases.
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
-pub struct Area {
+pub struct Area<S = f32> {
     /// Origin - usually the left-most X
-    pub x: f32,
+    pub x: S,
     /// Origin - usually the upper-most Y
-    pub y: f32,
+    pub y: S,
     /// Available width, starting at `x`
-    pub width: f32,
+    pub width: S,
     /// Available height, starting at `y`
-    pub height: f32,
+    pub height: S,
 }
 
 impl Area {
     /// Creates a new [`Area`].
+    ///
+    /// Areas with other scalar types are created with a struct literal, so `Area::new(..)` with float literals stays `f32`.
     pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
         Self {
             x,
@@ -97,42 +465,293 @@ impl Area {
             height,
         }
     }
+}
+
+impl<S: Scalar> Area<S> {
+    /// Whether the point at `x`, `y` is inside the area.
+    /// Points on the leading & top edges are inside, points on the trailing & bottom edges are not.
+    pub fn contains(&self, x: S, y: S) -> bool {
+        x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
+    }
+    /// The area mirrored horizontally within `within`
+    pub(crate) fn mirrored(&self, within: Area<S>) -> Area<S> {
+        Area {
+            x: within.x + (within.x + within.width) - (self.x + self.width),
+            ..*self
+        }
+    }
+    /// The area covered by both `self` & `other`, with no size if they don't overlap
+    ///
+    /// ```rust
+    /// use backer::models::*;
+    ///
+    /// let a: Area<f64> = Area { x: 1e9, y: 0., width: 100., height: 100. };
+    /// let b = Area { x: 1e9 + 50., ..a };
+    /// assert_eq!(a.intersection(b), Area { x: 1e9 + 50., y: 0., width: 50., height: 100. });
+    /// ```
+    pub fn intersection(&self, other: Area<S>) -> Area<S> {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        Area {
+            x,
+            y,
+            width: ((self.x + self.width).min(other.x + other.width) - x).max(S::ZERO),
+            height: ((self.y + self.height).min(other.y + other.height) - y).max(S::ZERO),
+        }
+    }
+    /// The area `t` of the way from `self` to `other`, where `t` is between 0 & 1
+    pub fn lerp(&self, other: Area<S>, t: S) -> Area<S> {
+        Area {
+            x: self.x + (other.x - self.x) * t,
+            y: self.y + (other.y - self.y) * t,
+            width: self.width + (other.width - self.width) * t,
+            height: self.height + (other.height - self.height) * t,
+        }
+    }
     #[allow(unused)]
     pub(crate) fn zero() -> Self {
         Self {
-            x: 0.,
-            y: 0.,
-            width: 0.,
-            height: 0.,
+            x: S::ZERO,
+            y: S::ZERO,
+            width: S::ZERO,
+            height: S::ZERO,
+        }
+    }
+    /// Converts the area to device pixels by multiplying it by `scale_factor`, & rounds each edge to the nearest pixel.
+    ///
+    /// Edges are rounded rather than positions & sizes, so areas that share an edge before conversion still do after.
+    ///
+    /// ```rust
+    /// use backer::models::*;
+    ///
+    /// let left = Area::new(0., 0., 33.3, 10.);
+    /// let right = Area::new(33.3, 0., 33.4, 10.);
+    /// assert_eq!(left.to_pixels(1.5), Area::new(0., 0., 50., 15.));
+    /// assert_eq!(right.to_pixels(1.5), Area::new(50., 0., 50., 15.));
+    /// ```
+    pub fn to_pixels(&self, scale_factor: S) -> Area<S> {
+        let x = round_edge(self.x * scale_factor);
+        let y = round_edge(self.y * scale_factor);
+        Area {
+            x,
+            y,
+            width: (round_edge((self.x + self.width) * scale_factor) - x).max(S::ZERO),
+            height: (round_edge((self.y + self.height) * scale_factor) - y).max(S::ZERO),
+        }
+    }
+    /// The character cells covered by the area, with each edge rounded to the nearest cell (see [`crate::Layout::cells`])
+    ///
+    /// Edges are rounded rather than positions & sizes, so areas that share an edge share it in cells too.
+    pub fn to_cells(&self) -> CellRect {
+        let cell = |value: S| round_edge(value).to_f64() as i32;
+        let (x, y) = (cell(self.x), cell(self.y));
+        CellRect {
+            x,
+            y,
+            width: (cell(self.x + self.width) - x).max(0) as u32,
+            height: (cell(self.y + self.height) - y).max(0) as u32,
         }
     }
 }
 
+/// A column or row definition for [`crate::nodes::grid`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Track<S = f32> {
+    /// A track with an exact size
+    Fixed(S),
+    /// A track bounded by an optional minimum & maximum size
+    Range {
+        /// The smallest size the track may take
+        min: Option<S>,
+        /// The largest size the track may take
+        max: Option<S>,
+    },
+    /// A track sized to fit its contents, sharing leftover space with other tracks in proportion to its fraction
+    Fraction(S),
+}
+
+impl<S: Scalar> Track<S> {
+    /// Creates a [`Track::Range`] from a range of sizes
+    pub fn range<R>(range: R) -> Self
+    where
+        R: RangeBounds<S>,
+    {
+        Track::Range {
+            min: match range.start_bound() {
+                Bound::Included(bound) | Bound::Excluded(bound) => Some(*bound),
+                Bound::Unbounded => None,
+            },
+            max: match range.end_bound() {
+                Bound::Included(bound) | Bound::Excluded(bound) => Some(*bound),
+                Bound::Unbounded => None,
+            },
+        }
+    }
+}
+
+/// The kind of a node in a computed layout (see [`crate::ComputedLayout`])
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NodeKind {
+    /// Created by padding modifiers like [`crate::NodeWith::pad`]
+    Padding,
+    /// Created by [`crate::nodes::column`]
+    Column,
+    /// Created by [`crate::nodes::row`]
+    Row,
+    /// Created by [`crate::nodes::stack`]
+    Stack,
+    /// Created by [`crate::nodes::row_wrap`] & [`crate::nodes::column_wrap`]
+    Wrap,
+    /// Created by [`crate::nodes::grid`]
+    Grid,
+    /// Created by [`crate::nodes::group`]
+    Group,
+    /// Created by [`crate::NodeWith::id`]
+    Identified,
+    /// Created by [`crate::NodeWith::clip`]
+    Clip,
+    /// Created by [`crate::NodeWith::direction`]
+    Direction,
+    /// Created by [`crate::NodeWith::animate`]
+    Animated,
+    /// Created by [`crate::NodeWith::transition`]
+    Transition,
+    /// Created by offset modifiers like [`crate::NodeWith::offset`]
+    Offset,
+    /// Created by [`crate::nodes::draw`]
+    Draw,
+    /// Created by size & alignment modifiers like [`crate::NodeWith::width`]
+    Explicit,
+    /// Created by [`crate::nodes::empty`]
+    Empty,
+    /// Created by [`crate::nodes::space`]
+    Space,
+    /// Created by [`crate::nodes::scope`]
+    Scope,
+    /// Created by [`crate::nodes::area_reader`]
+    AreaReader,
+    /// Created by [`crate::nodes::scroll`]
+    Scroll,
+    /// Created by [`crate::nodes::virtual_column`] & [`crate::nodes::virtual_row`]
+    Virtual,
+    /// Created by [`crate::NodeWith::attach_over`] & [`crate::NodeWith::attach_under`]
+    Coupled {
+        /// Whether the attached node is drawn over the node it's attached to
+        over: bool,
+    },
+}
+
+/// A node in a computed layout, with the area it was allocated (see [`crate::ComputedLayout`])
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComputedNode<S = f32> {
+    /// The kind of node
+    pub kind: NodeKind,
+    /// The hashed id of the node, for nodes created by [`crate::NodeWith::id`]
+    pub id: Option<u64>,
+    /// The area allocated to the node after layout
+    pub area: Area<S>,
+    /// The node's children, in the order they were defined
+    pub children: Vec<ComputedNode<S>>,
+}
+
+/// A node's layout, passed to the callback set with [`crate::Layout::debug`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebugNode<S = f32> {
+    /// The kind of node
+    pub kind: NodeKind,
+    /// The hashed id of the node, for nodes created by [`crate::NodeWith::id`]
+    pub id: Option<u64>,
+    /// How many ancestors the node has
+    pub depth: usize,
+    /// The area allocated to the node by its parent
+    pub area: Area<S>,
+    /// The size limits the node computed from its modifiers & children
+    pub constraints: NodeConstraints<S>,
+    /// The space between the node's area & its child, for padding nodes
+    pub padding: Vec<Area<S>>,
+    /// The space between each of the node's children, for spaced rows & columns
+    pub spacing: Vec<Area<S>>,
+}
+
+/// A node with a minimum size larger than its maximum size, like `.width_range(20.0..10.)`
+/// (see [`crate::ComputedLayout::conflicts`])
+///
+/// Rather than failing the layout, conflicts are resolved by using the minimum size as the maximum size too.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstraintConflict<S = f32> {
+    /// The index of each node within its parent, from the root's child to the conflicting node
+    pub path: Vec<usize>,
+    /// The hashed id of the closest identified node containing the conflicting node
+    /// (see [`crate::NodeWith::id`])
+    pub id: Option<u64>,
+    /// The dimension with conflicting limits
+    pub dimension: Dimension,
+    /// The minimum size set on the node
+    pub min: S,
+    /// The maximum size set on the node
+    pub max: S,
+    /// The size the node was limited to instead
+    pub resolved: S,
+}
+
+/// The width or height of a node
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dimension {
+    /// The size along the x axis
+    Width,
+    /// The size along the y axis
+    Height,
+}
+
+/// The size limits of a node (see [`DebugNode`])
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NodeConstraints<S = f32> {
+    /// The smallest width the node can be
+    pub width_min: Option<S>,
+    /// The largest width the node can be
+    pub width_max: Option<S>,
+    /// The smallest height the node can be
+    pub height_min: Option<S>,
+    /// The largest height the node can be
+    pub height_max: Option<S>,
+    /// The aspect ratio the node keeps, as width / height
+    pub aspect: Option<S>,
+    /// Whether the node expands to fill available width
+    pub expand_x: bool,
+    /// Whether the node expands to fill available height
+    pub expand_y: bool,
+}
+
 #[derive(Debug, Clone, Copy)]
-pub(crate) struct Padding {
-    pub(crate) leading: f32,
-    pub(crate) trailing: f32,
-    pub(crate) top: f32,
-    pub(crate) bottom: f32,
+pub(crate) struct Padding<S = f32> {
+    pub(crate) leading: S,
+    pub(crate) trailing: S,
+    pub(crate) top: S,
+    pub(crate) bottom: S,
 }
 
-type DimensionFn<A, B> = Option<Rc<dyn Fn(f32, &mut A, &mut B) -> f32>>;
+type DimensionFn<A, B, S> = Option<shared_fn!(Fn(S, &mut A, &mut B) -> S)>;
+type MeasureFn<A, B, S> = Option<shared_fn!(Fn(ProposedSize<S>, &mut A, &mut B) -> (S, S))>;
 
-pub(crate) struct Size<A, B> {
-    pub(crate) width_min: Option<f32>,
-    pub(crate) width_max: Option<f32>,
-    pub(crate) height_min: Option<f32>,
-    pub(crate) height_max: Option<f32>,
+pub(crate) struct Size<A, B, S = f32> {
+    pub(crate) width_min: Option<S>,
+    pub(crate) width_max: Option<S>,
+    pub(crate) height_min: Option<S>,
+    pub(crate) height_max: Option<S>,
     pub(crate) x_align: Option<XAlign>,
     pub(crate) y_align: Option<YAlign>,
-    pub(crate) aspect: Option<f32>,
-    pub(crate) dynamic_height: DimensionFn<A, B>,
-    pub(crate) dynamic_width: DimensionFn<A, B>,
+    pub(crate) aspect: Option<S>,
+    pub(crate) dynamic_height: DimensionFn<A, B, S>,
+    pub(crate) dynamic_width: DimensionFn<A, B, S>,
+    pub(crate) measure: MeasureFn<A, B, S>,
     pub(crate) expand_x: bool,
     pub(crate) expand_y: bool,
+    pub(crate) column_span: Option<usize>,
+    pub(crate) row_span: Option<usize>,
+    pub(crate) weight: Option<S>,
 }
 
-impl<A, B> Clone for Size<A, B> {
+impl<A, B, S: Scalar> Clone for Size<A, B, S> {
     fn clone(&self) -> Self {
         Self {
             width_min: self.width_min,
@@ -144,14 +763,18 @@ impl<A, B> Clone for Size<A, B> {
             aspect: self.aspect,
             dynamic_height: self.dynamic_height.clone(),
             dynamic_width: self.dynamic_width.clone(),
+            measure: self.measure.clone(),
             expand_x: self.expand_x,
             expand_y: self.expand_y,
+            column_span: self.column_span,
+            row_span: self.row_span,
+            weight: self.weight,
         }
     }
 }
 
-impl<A, B> std::fmt::Debug for Size<A, B> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<A, B, S: Scalar> core::fmt::Debug for Size<A, B, S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("Size")
             .field("width_min", &self.width_min)
             .field("width_max", &self.width_max)
@@ -162,19 +785,23 @@ impl<A, B> std::fmt::Debug for Size<A, B> {
             .field("aspect", &self.aspect)
             .field("dynamic_height", &"<function>")
             .field("dynamic_width", &"<function>")
+            .field("measure", &"<function>")
             .field("expand_x", &self.expand_x)
             .field("expand_y", &self.expand_y)
+            .field("column_span", &self.column_span)
+            .field("row_span", &self.row_span)
+            .field("weight", &self.weight)
             .finish()
     }
 }
 
-impl<A, B> Default for Size<A, B> {
+impl<A, B, S: Scalar> Default for Size<A, B, S> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl<A, B> Size<A, B> {
+impl<A, B, S: Scalar> Size<A, B, S> {
     /// Creates a default size object to add constraints to
     pub(crate) fn new() -> Self {
         Size {
@@ -187,8 +814,34 @@ impl<A, B> Size<A, B> {
             aspect: None,
             dynamic_height: None,
             dynamic_width: None,
+            measure: None,
             expand_x: false,
             expand_y: false,
+            column_span: None,
+            row_span: None,
+            weight: None,
         }
     }
 }
+
+/// A layout that can't be computed, returned by [`crate::Layout::try_draw`] & [`crate::Layout::try_compute`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LayoutError<S = f32> {
+    /// The area to lay out in has a position or size that isn't finite, or a negative size
+    InvalidArea(Area<S>),
+    /// Multiple nodes in the same scope use this hashed id (see [`crate::NodeWith::id`])
+    DuplicateId(u64),
+}
+
+impl<S: Scalar> core::fmt::Display for LayoutError<S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            LayoutError::InvalidArea(area) => write!(f, "Can't lay out in the area {area:?}"),
+            LayoutError::DuplicateId(id) => {
+                write!(f, "Multiple nodes in the same scope use the id {id:#018x}")
+            }
+        }
+    }
+}
+
+impl<S: Scalar> core::error::Error for LayoutError<S> {}
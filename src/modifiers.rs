@@ -1,16 +1,25 @@
-use crate::{layout::NodeValue, models::*, node_cache::NodeCache, NodeWith};
-use std::{ops::RangeBounds, rc::Rc};
+use crate::prelude::*;
+use crate::{
+    computed::hash_id,
+    layout::NodeValue,
+    models::*,
+    node_cache::NodeCache,
+    shared::Shared,
+    traits::{MaybeSync, Scalar},
+    NodeWith,
+};
+use core::{hash::Hash, ops::RangeBounds, time::Duration};
 
-impl<State> NodeWith<State, ()> {
+impl<State, S: Scalar> NodeWith<State, (), S> {
     /// Constrains the node's height as a function of available width.
     ///
     /// Generally you should prefer size constraints, aspect ratio constraints or area readers over dynamic height.
     ///
     /// **This is primarily for UI elements such as text** where node height must depend on available width & scaling is
     /// not a simple option.
-    pub fn dynamic_height(self, f: impl Fn(f32, &mut State) -> f32 + 'static) -> Self {
+    pub fn dynamic_height(self, f: impl Fn(S, &mut State) -> S + MaybeSync + 'static) -> Self {
         self.wrap_or_update_explicit(Size {
-            dynamic_height: Some(Rc::new(move |h, a, _| f(h, a))),
+            dynamic_height: Some(Shared::new(move |h, a, _| f(h, a))),
             ..Default::default()
         })
     }
@@ -20,15 +29,23 @@ impl<State> NodeWith<State, ()> {
     ///
     /// **This is primarily for UI elements such as text** where node width must depend on available height & scaling is
     /// not a simple option.
-    pub fn dynamic_width(self, f: impl Fn(f32, &mut State) -> f32 + 'static) -> Self {
+    pub fn dynamic_width(self, f: impl Fn(S, &mut State) -> S + MaybeSync + 'static) -> Self {
         self.wrap_or_update_explicit(Size {
-            dynamic_width: Some(Rc::new(move |h, a, _| f(h, a))),
+            dynamic_width: Some(Shared::new(move |h, a, _| f(h, a))),
             ..Default::default()
         })
     }
+    /// Measures each element of a [`crate::nodes::virtual_column`] or [`crate::nodes::virtual_row`] with `extent`,
+    /// instead of giving every element the same extent (see [`NodeWith::item_extents_with`]).
+    pub fn item_extents(
+        self,
+        extent: impl Fn(usize, &mut State) -> S + MaybeSync + 'static,
+    ) -> Self {
+        self.item_extents_with(move |i, state, _| extent(i, state))
+    }
 }
 
-impl<State, Ctx> NodeWith<State, Ctx> {
+impl<State, Ctx, S: Scalar> NodeWith<State, Ctx, S> {
     /// Constrains the node's height as a function of available width.
     ///
     /// Generally you should prefer size constraints, aspect ratio constraints or area readers over dynamic height.
@@ -37,10 +54,10 @@ impl<State, Ctx> NodeWith<State, Ctx> {
     /// not a simple option.
     pub fn dynamic_height_with(
         self,
-        f: impl Fn(f32, &mut State, &mut Ctx) -> f32 + 'static,
+        f: impl Fn(S, &mut State, &mut Ctx) -> S + MaybeSync + 'static,
     ) -> Self {
         self.wrap_or_update_explicit(Size {
-            dynamic_height: Some(Rc::new(f)),
+            dynamic_height: Some(Shared::new(f)),
             ..Default::default()
         })
     }
@@ -52,64 +69,64 @@ impl<State, Ctx> NodeWith<State, Ctx> {
     /// not a simple option.
     pub fn dynamic_width_with(
         self,
-        f: impl Fn(f32, &mut State, &mut Ctx) -> f32 + 'static,
+        f: impl Fn(S, &mut State, &mut Ctx) -> S + MaybeSync + 'static,
     ) -> Self {
         self.wrap_or_update_explicit(Size {
-            dynamic_width: Some(Rc::new(f)),
+            dynamic_width: Some(Shared::new(f)),
             ..Default::default()
         })
     }
     /// Adds padding to the node along the leading edge
-    pub fn pad_leading(self, amount: f32) -> NodeWith<State, Ctx> {
+    pub fn pad_leading(self, amount: S) -> NodeWith<State, Ctx, S> {
         NodeWith {
             inner: NodeValue::Padding {
                 amounts: Padding {
                     leading: amount,
-                    trailing: 0.,
-                    top: 0.,
-                    bottom: 0.,
+                    trailing: S::ZERO,
+                    top: S::ZERO,
+                    bottom: S::ZERO,
                 },
                 element: Box::new(NodeCache::new(self.inner)),
             },
         }
     }
     /// Adds horizontal padding to the node (leading & trailing)
-    pub fn pad_x(self, amount: f32) -> NodeWith<State, Ctx> {
+    pub fn pad_x(self, amount: S) -> NodeWith<State, Ctx, S> {
         NodeWith {
             inner: NodeValue::Padding {
                 amounts: Padding {
                     leading: amount,
                     trailing: amount,
-                    top: 0.,
-                    bottom: 0.,
+                    top: S::ZERO,
+                    bottom: S::ZERO,
                 },
                 element: Box::new(NodeCache::new(self.inner)),
             },
         }
     }
     /// Adds padding to the node along the trailing edge
-    pub fn pad_trailing(self, amount: f32) -> NodeWith<State, Ctx> {
+    pub fn pad_trailing(self, amount: S) -> NodeWith<State, Ctx, S> {
         NodeWith {
             inner: NodeValue::Padding {
                 amounts: Padding {
-                    leading: 0.,
+                    leading: S::ZERO,
                     trailing: amount,
-                    top: 0.,
-                    bottom: 0.,
+                    top: S::ZERO,
+                    bottom: S::ZERO,
                 },
                 element: Box::new(NodeCache::new(self.inner)),
             },
         }
     }
     /// Adds padding to the node along the top edge
-    pub fn pad_top(self, amount: f32) -> NodeWith<State, Ctx> {
+    pub fn pad_top(self, amount: S) -> NodeWith<State, Ctx, S> {
         NodeWith {
             inner: NodeValue::Padding {
                 amounts: Padding {
-                    leading: 0.,
-                    trailing: 0.,
+                    leading: S::ZERO,
+                    trailing: S::ZERO,
                     top: amount,
-                    bottom: 0.,
+                    bottom: S::ZERO,
                 },
                 element: Box::new(NodeCache::new(self.inner)),
             },
@@ -117,12 +134,12 @@ impl<State, Ctx> NodeWith<State, Ctx> {
     }
 
     /// Adds vertical padding to the node (top & bottom)
-    pub fn pad_y(self, amount: f32) -> NodeWith<State, Ctx> {
+    pub fn pad_y(self, amount: S) -> NodeWith<State, Ctx, S> {
         NodeWith {
             inner: NodeValue::Padding {
                 amounts: Padding {
-                    leading: 0.,
-                    trailing: 0.,
+                    leading: S::ZERO,
+                    trailing: S::ZERO,
                     top: amount,
                     bottom: amount,
                 },
@@ -131,13 +148,13 @@ impl<State, Ctx> NodeWith<State, Ctx> {
         }
     }
     /// Adds padding to the node along the bottom edge
-    pub fn pad_bottom(self, amount: f32) -> NodeWith<State, Ctx> {
+    pub fn pad_bottom(self, amount: S) -> NodeWith<State, Ctx, S> {
         NodeWith {
             inner: NodeValue::Padding {
                 amounts: Padding {
-                    leading: 0.,
-                    trailing: 0.,
-                    top: 0.,
+                    leading: S::ZERO,
+                    trailing: S::ZERO,
+                    top: S::ZERO,
                     bottom: amount,
                 },
                 element: Box::new(NodeCache::new(self.inner)),
@@ -145,7 +162,7 @@ impl<State, Ctx> NodeWith<State, Ctx> {
         }
     }
     /// Adds padding to the node on all sides
-    pub fn pad(self, amount: f32) -> NodeWith<State, Ctx> {
+    pub fn pad(self, amount: S) -> NodeWith<State, Ctx, S> {
         NodeWith {
             inner: NodeValue::Padding {
                 amounts: Padding {
@@ -158,14 +175,182 @@ impl<State, Ctx> NodeWith<State, Ctx> {
             },
         }
     }
+    /// Identifies the node with `id` so its area can be found after layout (see [`crate::ComputedLayout::area`])
+    ///
+    /// Ids must be unique within their scope (see [`crate::nodes::scope`]).
+    /// Duplicate ids are reported by a panic in debug builds, which shows the hashed id as it's exported
+    /// by [`crate::ComputedLayout::to_json`].
+    /// Integer ids compare by value whatever their type, so a node identified with `1usize` is found by `area(1)`.
+    pub fn id(self, id: impl Hash) -> NodeWith<State, Ctx, S> {
+        NodeWith {
+            inner: NodeValue::Identified {
+                id: hash_id(&id),
+                element: Box::new(NodeCache::new(self.inner)),
+            },
+        }
+    }
+    /// Clips the node & its children to the area the node occupies.
+    ///
+    /// Draw nodes created with [`crate::nodes::draw_clipped`] receive the clip area,
+    /// which is the intersection of the areas of every clipping ancestor (including [`crate::nodes::scroll`] nodes).
+    /// Draw nodes that are entirely outside of the clip area aren't drawn,
+    /// & clipped nodes outside of the clip area aren't found by [`crate::ComputedLayout::hit_test`].
+    ///
+    /// ```rust
+    /// use backer::*;
+    /// use backer::models::*;
+    /// use backer::nodes::*;
+    ///
+    /// Layout::new(|()| {
+    ///     stack(vec![
+    ///         draw_clipped(|area, clip, _| {
+    ///             assert_eq!(area, Area::new(40., 30., 20., 20.));
+    ///             assert_eq!(clip, Some(Area::new(40., 40., 20., 20.)));
+    ///         })
+    ///         .width(20.)
+    ///         .height(20.)
+    ///         .offset_y(-10.),
+    ///     ])
+    ///     .clip()
+    /// })
+    /// .draw(Area::new(0., 0., 100., 100.), &mut ());
+    /// ```
+    pub fn clip(self) -> NodeWith<State, Ctx, S> {
+        NodeWith {
+            inner: NodeValue::Clip {
+                element: Box::new(NodeCache::new(self.inner)),
+            },
+        }
+    }
+    /// Overrides the direction the node & its children are laid out in (see [`crate::Layout::direction`])
+    ///
+    /// ```rust
+    /// use backer::*;
+    /// use backer::models::*;
+    /// use backer::nodes::*;
+    ///
+    /// Layout::new(|()| {
+    ///     row(vec![
+    ///         draw(|a, _| assert_eq!(a, Area::new(90., 0., 10., 100.))).width(10.),
+    ///         draw(|a, _| assert_eq!(a, Area::new(10., 0., 80., 100.))).pad_trailing(10.),
+    ///     ])
+    ///     .direction(Direction::RightToLeft)
+    /// })
+    /// .draw(Area::new(0., 0., 100., 100.), &mut ());
+    /// ```
+    pub fn direction(self, direction: Direction) -> NodeWith<State, Ctx, S> {
+        NodeWith {
+            inner: NodeValue::Direction {
+                direction,
+                element: Box::new(NodeCache::new(self.inner)),
+            },
+        }
+    }
+    /// Animates changes to the node's area between frames, over `duration` with `easing`.
+    ///
+    /// The node must be identified with [`NodeWith::id`] before it's animated, so it can be matched between frames.
+    /// Modifiers can be applied between the two, like `.id("a").pad(5.).animate(..)`,
+    /// but nodes without an id aren't animated (& panic in debug builds).
+    /// When the node's area changes, the node & its children are drawn in an area moving from the previous area
+    /// to the new area, scaling children to fit. Animations are driven by the time passed to [`crate::Layout::set_time`],
+    /// & are kept by the [`crate::Layout`], so it must be kept between frames.
+    ///
+    /// ```rust
+    /// use backer::*;
+    /// use backer::models::*;
+    /// use backer::nodes::*;
+    /// use std::time::Duration;
+    ///
+    /// let mut layout = Layout::new(|wide: &mut bool| {
+    ///     draw(|_, _| {})
+    ///         .width(if *wide { 60. } else { 20. })
+    ///         .align(Align::Leading)
+    ///         .id("panel")
+    ///         .animate(Duration::from_secs(1), Easing::Linear)
+    /// });
+    /// let area = Area::new(0., 0., 100., 100.);
+    /// layout.compute(area, &mut false);
+    /// // The animation starts on the first frame the area changes
+    /// layout.set_time(Duration::from_secs(1));
+    /// layout.compute(area, &mut true);
+    /// layout.set_time(Duration::from_millis(1500));
+    /// let computed = layout.compute(area, &mut true);
+    /// assert_eq!(computed.area("panel"), Some(Area::new(0., 0., 40., 100.)));
+    /// ```
+    pub fn animate(self, duration: Duration, easing: Easing) -> NodeWith<State, Ctx, S> {
+        let id = self.inner.wrapped_id();
+        debug_assert!(id.is_some(), "Animated nodes must be identified with `id`");
+        NodeWith {
+            inner: NodeValue::Animated {
+                id,
+                duration,
+                easing,
+                element: Box::new(NodeCache::new(self.inner)),
+            },
+        }
+    }
+    /// Transitions the node in when it's added to the layout, & out when it's removed.
+    ///
+    /// The node must be identified with [`NodeWith::id`] before it's transitioned, so it can be matched between frames.
+    /// As with [`NodeWith::animate`], modifiers can be applied between the two,
+    /// but nodes without an id aren't transitioned (& panic in debug builds).
+    /// Like [`NodeWith::animate`], transitions are driven by the time passed to [`crate::Layout::set_time`]
+    /// & nodes don't transition in on the first frame a [`crate::Layout`] computes.
+    ///
+    /// When a node inside a [`crate::nodes::row`], [`crate::nodes::column`] or [`crate::nodes::stack`] is removed,
+    /// it's kept in its place while it leaves, so its siblings only move once the transition has finished -
+    /// use [`NodeWith::animate`] on siblings to move them smoothly. Leaving nodes are redrawn with the draw closures
+    /// & areas from the last frame they were in, not including any nodes inside a [`crate::nodes::scope`].
+    ///
+    /// ```rust
+    /// use backer::*;
+    /// use backer::models::*;
+    /// use backer::nodes::*;
+    /// use std::time::Duration;
+    ///
+    /// let mut layout = Layout::new(|shown: &mut bool| {
+    ///     column(vec![
+    ///         draw(|_, _| {}).height(10.),
+    ///         if *shown {
+    ///             draw_faded(|_, opacity, _| assert_eq!(opacity, 0.5))
+    ///                 .height(10.)
+    ///                 .id("toast")
+    ///                 .transition(Transition::new(Duration::from_secs(1), Easing::Linear).fade())
+    ///         } else {
+    ///             empty()
+    ///         },
+    ///     ])
+    /// });
+    /// let area = Area::new(0., 0., 100., 100.);
+    /// layout.compute(area, &mut true);
+    /// // The node leaves on the first frame it's missing from the layout
+    /// layout.set_time(Duration::from_secs(1));
+    /// layout.compute(area, &mut false);
+    /// layout.set_time(Duration::from_millis(1500));
+    /// layout.draw(area, &mut false);
+    /// ```
+    pub fn transition(self, transition: Transition) -> NodeWith<State, Ctx, S> {
+        let id = self.inner.wrapped_id();
+        debug_assert!(
+            id.is_some(),
+            "Transitioned nodes must be identified with `id`"
+        );
+        NodeWith {
+            inner: NodeValue::Transition {
+                id,
+                transition,
+                element: Box::new(NodeCache::new(self.inner)),
+            },
+        }
+    }
     /// Offsets the node along the x axis.
     /// This is an absolute offset that simply shifts nodes away from their calculated position
     /// This won't impact layout besides child nodes also being offset
-    pub fn offset_x(self, amount: f32) -> NodeWith<State, Ctx> {
+    pub fn offset_x(self, amount: S) -> NodeWith<State, Ctx, S> {
         NodeWith {
             inner: NodeValue::Offset {
                 offset_x: amount,
-                offset_y: 0.,
+                offset_y: S::ZERO,
                 element: Box::new(NodeCache::new(self.inner)),
             },
         }
@@ -173,10 +358,10 @@ impl<State, Ctx> NodeWith<State, Ctx> {
     /// Offsets the node along the y axis.
     /// This is an absolute offset that simply shifts nodes away from their calculated position
     /// This won't impact layout besides child nodes also being offset
-    pub fn offset_y(self, amount: f32) -> NodeWith<State, Ctx> {
+    pub fn offset_y(self, amount: S) -> NodeWith<State, Ctx, S> {
         NodeWith {
             inner: NodeValue::Offset {
-                offset_x: 0.,
+                offset_x: S::ZERO,
                 offset_y: amount,
                 element: Box::new(NodeCache::new(self.inner)),
             },
@@ -185,7 +370,7 @@ impl<State, Ctx> NodeWith<State, Ctx> {
     /// Offsets the node along the x & y axis.
     /// This is an absolute offset that simply shifts nodes away from their calculated position
     /// This won't impact layout besides child nodes also being offset
-    pub fn offset(self, offset_x: f32, offset_y: f32) -> NodeWith<State, Ctx> {
+    pub fn offset(self, offset_x: S, offset_y: S) -> NodeWith<State, Ctx, S> {
         NodeWith {
             inner: NodeValue::Offset {
                 offset_x,
@@ -195,7 +380,7 @@ impl<State, Ctx> NodeWith<State, Ctx> {
         }
     }
     /// Specifies an explicit width for a node
-    pub fn width(self, width: f32) -> Self {
+    pub fn width(self, width: S) -> Self {
         self.wrap_or_update_explicit(Size {
             width_min: Some(width),
             width_max: Some(width),
@@ -204,7 +389,7 @@ impl<State, Ctx> NodeWith<State, Ctx> {
         })
     }
     /// Specifies an explicit height for a node
-    pub fn height(self, height: f32) -> Self {
+    pub fn height(self, height: S) -> Self {
         self.wrap_or_update_explicit(Size {
             height_min: Some(height),
             height_max: Some(height),
@@ -215,18 +400,18 @@ impl<State, Ctx> NodeWith<State, Ctx> {
     /// Specifies bounds on a node's height
     pub fn height_range<R>(self, range: R) -> Self
     where
-        R: RangeBounds<f32>,
+        R: RangeBounds<S>,
     {
         self.wrap_or_update_explicit(Size {
             height_min: match range.start_bound() {
-                std::ops::Bound::Included(bound) => Some(*bound),
-                std::ops::Bound::Excluded(bound) => Some(*bound),
-                std::ops::Bound::Unbounded => None,
+                core::ops::Bound::Included(bound) => Some(*bound),
+                core::ops::Bound::Excluded(bound) => Some(*bound),
+                core::ops::Bound::Unbounded => None,
             },
             height_max: match range.end_bound() {
-                std::ops::Bound::Included(bound) => Some(*bound),
-                std::ops::Bound::Excluded(bound) => Some(*bound),
-                std::ops::Bound::Unbounded => None,
+                core::ops::Bound::Included(bound) => Some(*bound),
+                core::ops::Bound::Excluded(bound) => Some(*bound),
+                core::ops::Bound::Unbounded => None,
             },
             expand_y: false,
             ..Default::default()
@@ -235,18 +420,18 @@ impl<State, Ctx> NodeWith<State, Ctx> {
     /// Specifies bounds on a node's width
     pub fn width_range<R>(self, range: R) -> Self
     where
-        R: RangeBounds<f32>,
+        R: RangeBounds<S>,
     {
         self.wrap_or_update_explicit(Size {
             width_min: match range.start_bound() {
-                std::ops::Bound::Included(bound) => Some(*bound),
-                std::ops::Bound::Excluded(bound) => Some(*bound),
-                std::ops::Bound::Unbounded => None,
+                core::ops::Bound::Included(bound) => Some(*bound),
+                core::ops::Bound::Excluded(bound) => Some(*bound),
+                core::ops::Bound::Unbounded => None,
             },
             width_max: match range.end_bound() {
-                std::ops::Bound::Included(bound) => Some(*bound),
-                std::ops::Bound::Excluded(bound) => Some(*bound),
-                std::ops::Bound::Unbounded => None,
+                core::ops::Bound::Included(bound) => Some(*bound),
+                core::ops::Bound::Excluded(bound) => Some(*bound),
+                core::ops::Bound::Unbounded => None,
             },
             expand_x: false,
             ..Default::default()
@@ -301,6 +486,96 @@ impl<State, Ctx> NodeWith<State, Ctx> {
             (Some(x), Some(y)) => self.x_align_contents(x).y_align_contents(y),
         }
     }
+    /// Distributes leftover space between the contents of a row or column instead of aligning them.
+    ///
+    /// This modifier must be used immediately after the row / column to take effect.
+    /// There is only leftover space to distribute if the contents are constrained to be smaller than the container,
+    /// so you may also want to use [`NodeWith::expand()`].
+    ///
+    /// ```rust
+    /// use backer::models::*;
+    /// use backer::nodes::*;
+    /// use backer::Layout;
+    ///
+    /// Layout::new(|()| {
+    ///     row(vec![
+    ///         draw(|a, _| {
+    ///             assert_eq!(a, Area::new(0., 0., 10., 100.));
+    ///         })
+    ///         .width(10.),
+    ///         draw(|a, _| {
+    ///             assert_eq!(a, Area::new(90., 0., 10., 100.));
+    ///         })
+    ///         .width(10.),
+    ///     ])
+    ///     .distribute(Distribution::SpaceBetween)
+    ///     .expand()
+    /// })
+    /// .draw(Area::new(0., 0., 100., 100.), &mut ());
+    /// ```
+    pub fn distribute(mut self, distribution: Distribution) -> Self {
+        match self.inner {
+            NodeValue::Column {
+                distribution: ref mut current,
+                ..
+            }
+            | NodeValue::Row {
+                distribution: ref mut current,
+                ..
+            } => *current = Some(distribution),
+            _ => (),
+        }
+        self
+    }
+    /// Sets how many elements outside of the visible area are built on either side of it,
+    /// for a [`crate::nodes::virtual_column`] or [`crate::nodes::virtual_row`]. Defaults to 2.
+    ///
+    /// This modifier must be used immediately after the virtual sequence to take effect.
+    pub fn overscan(mut self, items: usize) -> Self {
+        if let NodeValue::Virtual {
+            ref mut overscan, ..
+        } = self.inner
+        {
+            *overscan = items;
+        }
+        self
+    }
+    /// Measures each element of a [`crate::nodes::virtual_column`] or [`crate::nodes::virtual_row`] with `extent`,
+    /// which is called with the element's index, instead of giving every element the same extent.
+    ///
+    /// `extent` is called for every element each time the sequence is laid out, so it should be cheap,
+    /// like looking up a height measured when the element's content changed.
+    /// Elements are still only built when they're visible.
+    /// This modifier must be used immediately after the virtual sequence to take effect.
+    ///
+    /// ```rust
+    /// use backer::*;
+    /// use backer::models::*;
+    /// use backer::nodes::*;
+    ///
+    /// let computed = Layout::new(|_: &mut ()| {
+    ///     scroll(
+    ///         ScrollAxis::Vertical,
+    ///         |_, _| (0., 0.),
+    ///         virtual_column(1000, 0., |i, _| draw(|_, _| {}).id(i))
+    ///             .item_extents(|i, _| if i % 2 == 0 { 10. } else { 30. }),
+    ///     )
+    /// })
+    /// .compute(Area::new(0., 0., 100., 100.), &mut ());
+    /// assert_eq!(computed.area(3_usize), Some(Area::new(0., 50., 100., 30.)));
+    /// ```
+    pub fn item_extents_with(
+        mut self,
+        extent: impl Fn(usize, &mut State, &mut Ctx) -> S + MaybeSync + 'static,
+    ) -> Self {
+        if let NodeValue::Virtual {
+            ref mut extents, ..
+        } = self.inner
+        {
+            *extents = Some(Shared::new(extent));
+        }
+        self
+    }
     fn x_align_contents(mut self, align: XAlign) -> Self {
         match self.inner {
             NodeValue::Column {
@@ -313,6 +588,12 @@ impl<State, Ctx> NodeWith<State, Ctx> {
             } => *row_align = Some(align),
             NodeValue::Stack {
                 ref mut x_align, ..
+            }
+            | NodeValue::Wrap {
+                ref mut x_align, ..
+            }
+            | NodeValue::Grid {
+                ref mut x_align, ..
             } => *x_align = Some(align),
             _ => (),
         }
@@ -330,6 +611,12 @@ impl<State, Ctx> NodeWith<State, Ctx> {
             } => *col_align = Some(align),
             NodeValue::Stack {
                 ref mut y_align, ..
+            }
+            | NodeValue::Wrap {
+                ref mut y_align, ..
+            }
+            | NodeValue::Grid {
+                ref mut y_align, ..
             } => *y_align = Some(align),
             _ => (),
         }
@@ -348,7 +635,7 @@ impl<State, Ctx> NodeWith<State, Ctx> {
         })
     }
     /// Constrains the node's height to `ratio` of width
-    pub fn aspect(self, ratio: f32) -> Self {
+    pub fn aspect(self, ratio: S) -> Self {
         self.wrap_or_update_explicit(Size {
             aspect: Some(ratio),
             ..Default::default()
@@ -385,6 +672,46 @@ impl<State, Ctx> NodeWith<State, Ctx> {
             ..Default::default()
         })
     }
+    /// Sets how much of the leftover space in a row or column this node takes, relative to its siblings.
+    ///
+    /// Siblings have a weight of `1.` by default, so a node with a weight of `2.` grows twice as much as
+    /// its unweighted siblings. Size constraints are still respected.
+    ///
+    /// ```rust
+    /// use backer::models::*;
+    /// use backer::nodes::*;
+    /// use backer::Layout;
+    ///
+    /// Layout::new(|()| {
+    ///     row(vec![
+    ///         draw(|a, _| {
+    ///             assert_eq!(a, Area::new(0., 0., 60., 90.));
+    ///         })
+    ///         .weight(2.),
+    ///         draw(|a, _| {
+    ///             assert_eq!(a, Area::new(60., 0., 30., 90.));
+    ///         }),
+    ///     ])
+    /// })
+    /// .draw(Area::new(0., 0., 90., 90.), &mut ());
+    /// ```
+    pub fn weight(self, weight: S) -> Self {
+        self.wrap_or_update_explicit(Size {
+            weight: Some(weight.max(S::ZERO)),
+            ..Default::default()
+        })
+    }
+    /// Makes the node cover `columns` columns & `rows` rows when placed in a [`crate::nodes::grid`].
+    ///
+    /// Spans wider than the grid are limited to the number of columns in the grid.
+    /// Spanning cells don't contribute to the size of the tracks they cover.
+    pub fn span(self, columns: usize, rows: usize) -> Self {
+        self.wrap_or_update_explicit(Size {
+            column_span: Some(columns),
+            row_span: Some(rows),
+            ..Default::default()
+        })
+    }
     /// Attaches `node` over this node as an overlay
     ///
     /// The area available to the attached node is the size of the node it's attached to.
@@ -411,7 +738,7 @@ impl<State, Ctx> NodeWith<State, Ctx> {
             },
         }
     }
-    fn wrap_or_update_explicit(mut self, size: Size<State, Ctx>) -> Self {
+    fn wrap_or_update_explicit(mut self, size: Size<State, Ctx, S>) -> Self {
         match self.inner {
             NodeValue::Explicit {
                 ref mut options,
@@ -436,17 +763,21 @@ impl<State, Ctx> NodeWith<State, Ctx> {
                         options.height_min
                     },
                     height_max: if height_update {
-                        size.height_min
+                        size.height_max
                     } else {
-                        options.height_min
+                        options.height_max
                     },
                     x_align: size.x_align.or(options.x_align),
                     y_align: size.y_align.or(options.y_align),
                     aspect: size.aspect.or(options.aspect),
                     dynamic_height: size.dynamic_height.or(options.dynamic_height.clone()),
                     dynamic_width: size.dynamic_width.or(options.dynamic_width.clone()),
+                    measure: size.measure.or(options.measure.clone()),
                     expand_x: size.expand_x || options.expand_x,
                     expand_y: size.expand_y || options.expand_y,
+                    column_span: size.column_span.or(options.column_span),
+                    row_span: size.row_span.or(options.row_span),
+                    weight: size.weight.or(options.weight),
                 };
             }
             _ => {
@@ -473,8 +804,19 @@ mod tests {
             .width(10.)
             .width_range(5.0..)
             .inner
-            .constraints(Area::zero(), &mut (), &mut ());
+            .constraints(Area::zero(), false, &mut (), &mut ());
         assert!(c.width.get_upper().is_none());
         assert_eq!(c.width.get_lower().unwrap(), 5.);
     }
+
+    #[test]
+    fn test_explicit_update_keeps_height_bounds() {
+        let c = space::<(), ()>()
+            .height_range(10.0..=50.)
+            .width(20.)
+            .inner
+            .constraints(Area::zero(), false, &mut (), &mut ());
+        assert_eq!(c.height.get_lower(), Some(10.));
+        assert_eq!(c.height.get_upper(), Some(50.));
+    }
 }
@@ -1,17 +1,20 @@
 use core::fmt;
-use std::fmt::{Debug, Formatter};
+use core::fmt::{Debug, Formatter};
 
-use crate::layout::NodeValue;
+use crate::{layout::NodeValue, traits::Scalar};
 
 /// A layout tree node. Use methods in [`crate::nodes`] to create nodes.
-pub type Node<State> = NodeWith<State, ()>;
+pub type Node<State, S = f32> = NodeWith<State, (), S>;
 
 /// A layout tree node. Use methods in [`crate::nodes`] to create nodes.
-pub struct NodeWith<State, Ctx> {
-    pub(crate) inner: NodeValue<State, Ctx>,
+///
+/// Nodes are laid out in `f32` by default, & can be laid out in any other [`Scalar`]
+/// by creating them with the associated functions of `NodeWith` instead.
+pub struct NodeWith<State, Ctx, S = f32> {
+    pub(crate) inner: NodeValue<State, Ctx, S>,
 }
 
-impl<State, Ctx> Debug for NodeWith<State, Ctx> {
+impl<State, Ctx, S: Scalar> Debug for NodeWith<State, Ctx, S> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.debug_struct("NodeWith")
             .field("inner", &self.inner)
@@ -1,72 +1,212 @@
-use std::fmt::Debug;
+use crate::prelude::*;
+use alloc::collections::BTreeSet;
+use core::fmt::Debug;
 
 use crate::{
+    animation::Animator,
     constraints::SizeConstraints,
     layout::NodeValue,
-    models::{Area, XAlign, YAlign},
-    traits::NodeTrait,
+    models::{Area, ComputedNode, ConstraintConflict, DebugNode, Direction, XAlign, YAlign},
+    retained::LayoutInput,
+    traits::{NodeTrait, Scalar},
 };
 
-pub(crate) struct NodeCache<State, Ctx> {
-    pub(crate) kind: NodeValue<State, Ctx>,
-    cache_area: Option<Area>,
-    cached_constraints: Option<SizeConstraints>,
+pub(crate) struct NodeCache<State, Ctx, S> {
+    pub(crate) kind: NodeValue<State, Ctx, S>,
+    cache_area: Option<Area<S>>,
+    cached_constraints: Option<SizeConstraints<S>>,
+    pub(crate) layout_area: Option<Area<S>>,
+    pub(crate) layout_input: Option<LayoutInput<S>>,
+    /// Whether this node & its children are unchanged since the previous frame (see [`crate::Layout::retained`])
+    pub(crate) clean: bool,
 }
 
-impl<State, Ctx> NodeCache<State, Ctx> {
-    pub(crate) fn new(kind: NodeValue<State, Ctx>) -> Self {
+impl<State, Ctx, S: Scalar> NodeCache<State, Ctx, S> {
+    pub(crate) fn new(kind: NodeValue<State, Ctx, S>) -> Self {
         Self {
             kind,
             cache_area: None,
             cached_constraints: None,
+            layout_area: None,
+            layout_input: None,
+            clean: false,
         }
     }
+    pub(crate) fn cached_constraints(&self) -> Option<(Area<S>, SizeConstraints<S>)> {
+        self.cache_area.zip(self.cached_constraints)
+    }
+    pub(crate) fn restore(
+        &mut self,
+        constraints: Option<(Area<S>, SizeConstraints<S>)>,
+        layout_input: Option<LayoutInput<S>>,
+        layout_area: Option<Area<S>>,
+    ) {
+        self.cache_area = constraints.map(|(area, _)| area);
+        self.cached_constraints = constraints.map(|(_, constraints)| constraints);
+        self.layout_input = layout_input;
+        self.layout_area = layout_area;
+    }
 }
 
-impl<State, Ctx> Debug for NodeCache<State, Ctx> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<State, Ctx, S: Scalar> NodeCache<State, Ctx, S> {
+    /// The area the node occupies after layout.
+    ///
+    /// Nodes that only position their child occupy the same area as their child,
+    /// which can be smaller than the area they were laid out in.
+    pub(crate) fn occupied_area(&self) -> Option<Area<S>> {
+        match &self.kind {
+            NodeValue::Explicit { element, .. }
+            | NodeValue::Identified { element, .. }
+            | NodeValue::Offset { element, .. }
+            | NodeValue::Clip { element }
+            | NodeValue::Direction { element, .. }
+            | NodeValue::Animated { element, .. }
+            | NodeValue::Transition { element, .. } => element.occupied_area(),
+            _ => self.layout_area,
+        }
+    }
+    /// The area the node will occupy when it's laid out in `available_area`, before it's laid out,
+    /// following the area each node that only positions its child allocates like [`NodeValue::layout`] does
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn occupied_area_in(
+        &mut self,
+        available_area: Area<S>,
+        contextual_x_align: Option<XAlign>,
+        contextual_y_align: Option<YAlign>,
+        direction: Direction,
+        cells: bool,
+        state: &mut State,
+        ctx: &mut Ctx,
+    ) -> Area<S> {
+        let offset = matches!(self.kind, NodeValue::Offset { .. });
+        let (x_align, y_align) = match self.kind {
+            NodeValue::Explicit { .. } | NodeValue::Offset { .. } => (None, None),
+            _ => (contextual_x_align, contextual_y_align),
+        };
+        let mut area = self.kind.allocate_area(
+            available_area,
+            contextual_x_align,
+            contextual_y_align,
+            cells,
+            state,
+            ctx,
+        )[0];
+        if direction == Direction::RightToLeft && !offset {
+            area = area.mirrored(available_area);
+        }
+        match &mut self.kind {
+            NodeValue::Direction { direction, element } => {
+                element.occupied_area_in(area, x_align, y_align, *direction, cells, state, ctx)
+            }
+            NodeValue::Explicit { element, .. }
+            | NodeValue::Identified { element, .. }
+            | NodeValue::Offset { element, .. }
+            | NodeValue::Clip { element }
+            | NodeValue::Animated { element, .. }
+            | NodeValue::Transition { element, .. } => {
+                element.occupied_area_in(area, x_align, y_align, direction, cells, state, ctx)
+            }
+            _ => available_area,
+        }
+    }
+}
+
+impl<State, Ctx, S: Scalar> Debug for NodeCache<State, Ctx, S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("NodeCache")
             .field("kind", &self.kind)
             .field("cache_area", &self.cache_area)
             .field("cached_constraints", &self.cached_constraints)
+            .field("layout_area", &self.layout_area)
+            .field("layout_input", &self.layout_input)
+            .field("clean", &self.clean)
             .finish()
     }
 }
 
-impl<State, Ctx> NodeTrait<State, Ctx> for NodeCache<State, Ctx> {
+impl<State, Ctx, S: Scalar> NodeTrait<State, Ctx, S> for NodeCache<State, Ctx, S> {
     fn constraints(
         &mut self,
-        available_area: Area,
+        available_area: Area<S>,
+        cells: bool,
         state: &mut State,
         ctx: &mut Ctx,
-    ) -> SizeConstraints {
+    ) -> SizeConstraints<S> {
         if let (Some(cache), Some(constraints)) = (self.cache_area, self.cached_constraints) {
             if cache == available_area {
                 return constraints;
             }
         }
-        let constraints = self.kind.constraints(available_area, state, ctx);
+        let constraints = self.kind.constraints(available_area, cells, state, ctx);
         self.cache_area = Some(available_area);
         self.cached_constraints = Some(constraints);
         constraints
     }
     fn layout(
         &mut self,
-        available_area: Area,
+        available_area: Area<S>,
         contextual_x_align: Option<XAlign>,
         contextual_y_align: Option<YAlign>,
+        visible: Area<S>,
+        direction: Direction,
+        cells: bool,
         state: &mut State,
         ctx: &mut Ctx,
     ) {
+        let input = (
+            available_area,
+            contextual_x_align,
+            contextual_y_align,
+            direction,
+        );
+        if self.clean && self.layout_input == Some(input) {
+            // Areas from the previous frame were restored when this node was reconciled
+            return;
+        }
+        self.layout_input = Some(input);
+        self.layout_area = Some(available_area);
         self.kind.layout(
             available_area,
             contextual_x_align,
             contextual_y_align,
+            visible,
+            direction,
+            cells,
             state,
             ctx,
         )
     }
-    fn draw(&mut self, state: &mut State, ctx: &mut Ctx) {
-        self.kind.draw(state, ctx)
+    fn draw(&mut self, clip: Option<Area<S>>, state: &mut State, ctx: &mut Ctx) {
+        self.kind.draw(clip, state, ctx)
+    }
+    fn computed(&self, _area: Area<S>) -> Vec<ComputedNode<S>> {
+        self.layout_area
+            .map(|area| self.kind.computed(area))
+            .into_iter()
+            .collect()
+    }
+    fn animate(&mut self, animator: &mut Animator<S>) {
+        self.kind.animate(animator)
+    }
+    fn map_areas(&mut self, f: &dyn Fn(Area<S>) -> Area<S>) {
+        self.layout_area = self.layout_area.map(f);
+        self.kind.map_areas(f)
+    }
+    fn fade(&mut self, opacity: f32) {
+        self.kind.fade(opacity)
+    }
+    fn debug_nodes(&self, depth: usize, nodes: &mut Vec<DebugNode<S>>) {
+        self.collect_debug(depth, nodes)
+    }
+    fn conflicts(
+        &self,
+        path: &mut Vec<usize>,
+        id: Option<u64>,
+        conflicts: &mut Vec<ConstraintConflict<S>>,
+    ) {
+        self.collect_conflicts(path, id, conflicts)
+    }
+    fn duplicate_id(&self) -> Option<u64> {
+        NodeCache::duplicate_id(self, &mut BTreeSet::new())
     }
 }
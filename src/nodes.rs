@@ -1,13 +1,15 @@
+use crate::prelude::*;
 use crate::{
     drawable::Drawable,
-    layout::NodeValue,
+    layout::{NodeValue, Orientation},
     models::*,
     node_cache::NodeCache,
+    shared::Shared,
     subtree::Subtree,
-    traits::{ScopableOption, VoidScoper},
+    traits::{MaybeSync, Scalar, ScopableOption, TextMeasurer, VoidScoper},
     Node, NodeWith,
 };
-use std::{marker::PhantomData, rc::Rc};
+use core::marker::PhantomData;
 
 macro_rules! container_doc {
     () => {
@@ -23,100 +25,378 @@ or pushing against other unconstrained nodes with equal force.
     };
 }
 
-/// Creates a vertical sequence of elements
+/// The scalar the functions in this module build nodes in
+type S = f32;
+
+/// Defines each constructor once, as a function of this module building `f32` nodes,
+/// & as an associated function of [`NodeWith`] building nodes in any [`Scalar`], which the function delegates to.
 ///
-#[doc = container_doc!()]
-pub fn column<State, Ctx>(elements: Vec<NodeWith<State, Ctx>>) -> NodeWith<State, Ctx> {
-    NodeWith {
-        inner: NodeValue::Column {
-            elements: filter_empty(ungroup(elements)),
-            spacing: 0.,
-            align: None,
-            off_axis_align: None,
-        },
-    }
+/// Signatures are written in terms of `S`, which is `f32` for the functions of this module.
+/// A constructor whose closures receive the context is written as `pub fn draw_with as draw(..)`,
+/// where `draw_with` is the function of this module & `draw` is the associated function.
+macro_rules! constructors {
+    (@collect [$($done:tt)*]) => {
+        $(constructors!(@function $done);)*
+
+        /// The node constructors of [`crate::nodes`], for layouts in any [`Scalar`].
+        ///
+        /// Their closures also receive the context, like the `_with` functions of [`crate::nodes`].
+        /// Outside of an `f32` layout, nodes are built with these, for example `NodeWith::<(), (), f64>::space()`.
+        impl<State, Ctx, S: Scalar> NodeWith<State, Ctx, S> {
+            $(constructors!(@associated $done);)*
+        }
+    };
+    (
+        @collect [$($done:tt)*]
+        $(#[$attr:meta])*
+        pub fn $function:ident as $associated:ident($($arg:ident: $ty:ty),* $(,)?)
+        $(where [$($bound:tt)*])?
+        $body:block
+        $($rest:tt)*
+    ) => {
+        constructors!(
+            @collect [$($done)* {
+                [$(#[$attr])*] $function $associated [$($arg: $ty),*] [$($($bound)*)?] $body
+            }]
+            $($rest)*
+        );
+    };
+    (
+        @collect [$($done:tt)*]
+        $(#[$attr:meta])*
+        pub fn $name:ident($($arg:ident: $ty:ty),* $(,)?)
+        $(where [$($bound:tt)*])?
+        $body:block
+        $($rest:tt)*
+    ) => {
+        constructors!(
+            @collect [$($done)* {
+                [$(#[$attr])*] $name $name [$($arg: $ty),*] [$($($bound)*)?] $body
+            }]
+            $($rest)*
+        );
+    };
+    (@function {
+        [$($attr:tt)*] $function:ident $associated:ident [$($arg:ident: $ty:ty),*] [$($bound:tt)*] $body:block
+    }) => {
+        $($attr)*
+        pub fn $function<State, Ctx>($($arg: $ty),*) -> NodeWith<State, Ctx>
+        where
+            $($bound)*
+        {
+            NodeWith::$associated($($arg),*)
+        }
+    };
+    (@associated {
+        [$($attr:tt)*] $function:ident $associated:ident [$($arg:ident: $ty:ty),*] [$($bound:tt)*] $body:block
+    }) => {
+        $($attr)*
+        pub fn $associated($($arg: $ty),*) -> Self
+        where
+            $($bound)*
+        $body
+    };
+    ($($constructors:tt)*) => {
+        constructors!(@collect [] $($constructors)*);
+    };
 }
-/// Creates multiple elements at once.
-/// Has no impact on layout.
-/// Just a convenience for adding a `Vec` of elements to a sequence node inline.
-/// ```rust
-/// use backer::*;
-/// use backer::models::*;
-/// use backer::nodes::*;
-/// use backer::traits::Scopable;
-///
-/// column::<(), ()>(vec![
-///     empty(),
-///     group(
-///         (0..5)
-///             .into_iter()
-///             .map(|i| empty())
-///             .collect()
-///     ),
-/// ]);
-/// ```
-pub fn group<State, Ctx>(elements: Vec<NodeWith<State, Ctx>>) -> NodeWith<State, Ctx> {
-    NodeWith {
-        inner: NodeValue::Group(filter_empty(ungroup(elements))),
+
+constructors! {
+    /// Creates a vertical sequence of elements
+    ///
+    #[doc = container_doc!()]
+    pub fn column(elements: Vec<NodeWith<State, Ctx, S>>) {
+        Self::column_spaced(S::ZERO, elements)
     }
-}
-/// Creates a vertical sequence of elements with the specified spacing between each element.
-///
-#[doc = container_doc!()]
-pub fn column_spaced<State, Ctx>(
-    spacing: f32,
-    elements: Vec<NodeWith<State, Ctx>>,
-) -> NodeWith<State, Ctx> {
-    NodeWith {
-        inner: NodeValue::Column {
-            elements: filter_empty(ungroup(elements)),
-            spacing,
-            align: None,
-            off_axis_align: None,
-        },
+    /// Creates multiple elements at once.
+    /// Has no impact on layout.
+    /// Just a convenience for adding a `Vec` of elements to a sequence node inline.
+    ///
+    /// Outside of a sequence, like at the root of a layout, the elements are stacked.
+    /// ```rust
+    /// use backer::*;
+    /// use backer::models::*;
+    /// use backer::nodes::*;
+    /// use backer::traits::Scopable;
+    ///
+    /// column::<(), ()>(vec![
+    ///     empty(),
+    ///     group(
+    ///         (0..5)
+    ///             .into_iter()
+    ///             .map(|i| empty())
+    ///             .collect()
+    ///     ),
+    /// ]);
+    /// ```
+    pub fn group(elements: Vec<NodeWith<State, Ctx, S>>) {
+        NodeWith {
+            inner: NodeValue::Group(filter_empty(ungroup(elements))),
+        }
     }
-}
-/// Creates a horizontal sequence of elements
-///
-#[doc = container_doc!()]
-pub fn row<State, Ctx>(elements: Vec<NodeWith<State, Ctx>>) -> NodeWith<State, Ctx> {
-    NodeWith {
-        inner: NodeValue::Row {
-            elements: filter_empty(ungroup(elements)),
-            spacing: 0.,
-            align: None,
-            off_axis_align: None,
-        },
+    /// Creates a vertical sequence of elements with the specified spacing between each element.
+    ///
+    #[doc = container_doc!()]
+    pub fn column_spaced(spacing: S, elements: Vec<NodeWith<State, Ctx, S>>) {
+        NodeWith {
+            inner: NodeValue::Column {
+                elements: filter_empty(ungroup(elements)),
+                spacing,
+                align: None,
+                off_axis_align: None,
+                distribution: None,
+            },
+        }
     }
-}
-/// Creates a horizontal sequence of elements with the specified spacing between each element.
-///
-#[doc = container_doc!()]
-pub fn row_spaced<State, Ctx>(
-    spacing: f32,
-    elements: Vec<NodeWith<State, Ctx>>,
-) -> NodeWith<State, Ctx> {
-    NodeWith {
-        inner: NodeValue::Row {
-            elements: filter_empty(ungroup(elements)),
-            spacing,
-            align: None,
-            off_axis_align: None,
-        },
+    /// Creates a horizontal sequence of elements
+    ///
+    #[doc = container_doc!()]
+    pub fn row(elements: Vec<NodeWith<State, Ctx, S>>) {
+        Self::row_spaced(S::ZERO, elements)
     }
-}
-/// Creates a sequence of elements to be laid out on top of each other.
-///
-#[doc = container_doc!()]
-pub fn stack<State, Ctx>(elements: Vec<NodeWith<State, Ctx>>) -> NodeWith<State, Ctx> {
-    NodeWith {
-        inner: NodeValue::Stack {
-            elements: filter_empty(ungroup(elements)),
-            x_align: None,
-            y_align: None,
-        },
+    /// Creates a horizontal sequence of elements with the specified spacing between each element.
+    ///
+    #[doc = container_doc!()]
+    pub fn row_spaced(spacing: S, elements: Vec<NodeWith<State, Ctx, S>>) {
+        NodeWith {
+            inner: NodeValue::Row {
+                elements: filter_empty(ungroup(elements)),
+                spacing,
+                align: None,
+                off_axis_align: None,
+                distribution: None,
+            },
+        }
+    }
+    /// Creates a horizontal sequence of elements that continues on a new line when the available width runs out
+    ///
+    /// Elements are moved to a new line based on their smallest width.
+    /// Elements with no width constraints take up a whole line.
+    ///
+    /// Alignment (see [`NodeWith::align_contents`]) applies to elements within each line & to the lines themselves.
+    ///
+    #[doc = container_doc!()]
+    pub fn row_wrap(elements: Vec<NodeWith<State, Ctx, S>>) {
+        Self::row_wrap_spaced(S::ZERO, S::ZERO, elements)
+    }
+    /// Creates a wrapping horizontal sequence of elements (see [`row_wrap`])
+    /// with `spacing` between elements & `line_spacing` between lines.
+    ///
+    #[doc = container_doc!()]
+    pub fn row_wrap_spaced(spacing: S, line_spacing: S, elements: Vec<NodeWith<State, Ctx, S>>) {
+        wrap(Orientation::Horizontal, spacing, line_spacing, elements)
+    }
+    /// Creates a vertical sequence of elements that continues in a new column when the available height runs out
+    ///
+    /// Elements are moved to a new column based on their smallest height.
+    /// Elements with no height constraints take up a whole column.
+    ///
+    /// Alignment (see [`NodeWith::align_contents`]) applies to elements within each column & to the columns themselves.
+    ///
+    #[doc = container_doc!()]
+    pub fn column_wrap(elements: Vec<NodeWith<State, Ctx, S>>) {
+        Self::column_wrap_spaced(S::ZERO, S::ZERO, elements)
+    }
+    /// Creates a wrapping vertical sequence of elements (see [`column_wrap`])
+    /// with `spacing` between elements & `line_spacing` between columns.
+    ///
+    #[doc = container_doc!()]
+    pub fn column_wrap_spaced(spacing: S, line_spacing: S, elements: Vec<NodeWith<State, Ctx, S>>) {
+        wrap(Orientation::Vertical, spacing, line_spacing, elements)
+    }
+    /// Creates a sequence of elements to be laid out on top of each other.
+    ///
+    #[doc = container_doc!()]
+    pub fn stack(elements: Vec<NodeWith<State, Ctx, S>>) {
+        NodeWith {
+            inner: NodeValue::Stack {
+                elements: filter_empty(ungroup(elements)),
+                x_align: None,
+                y_align: None,
+            },
+        }
+    }
+    /// Creates a grid of elements.
+    /// Elements fill the cells defined by `columns` & `rows` left to right, top to bottom.
+    ///
+    /// Tracks are sized according to their [`Track`] definition & the constraints of the elements they contain.
+    /// Rows are added as needed beyond those defined by `rows`, sized like [`Track::Fraction`] with a fraction of `1.`
+    ///
+    /// Use [`NodeWith::span`] to make an element cover multiple cells.
+    /// ```rust
+    /// use backer::models::*;
+    /// use backer::nodes::*;
+    ///
+    /// grid::<(), ()>(
+    ///     vec![Track::Fixed(100.), Track::Fraction(1.)],
+    ///     vec![],
+    ///     vec![
+    ///         draw(|_, _| {}),
+    ///         draw(|_, _| {}),
+    ///         draw(|_, _| {}).span(2, 1),
+    ///     ],
+    /// );
+    /// ```
+    ///
+    #[doc = container_doc!()]
+    pub fn grid(
+        columns: Vec<Track<S>>,
+        rows: Vec<Track<S>>,
+        elements: Vec<NodeWith<State, Ctx, S>>,
+    ) {
+        Self::grid_spaced(S::ZERO, S::ZERO, columns, rows, elements)
+    }
+    /// Creates a grid of elements with the specified spacing between each column & row (see [`grid`]).
+    ///
+    #[doc = container_doc!()]
+    pub fn grid_spaced(
+        column_spacing: S,
+        row_spacing: S,
+        columns: Vec<Track<S>>,
+        rows: Vec<Track<S>>,
+        elements: Vec<NodeWith<State, Ctx, S>>,
+    ) {
+        NodeWith {
+            inner: NodeValue::Grid {
+                elements: filter_empty(ungroup(elements)),
+                columns,
+                rows,
+                column_spacing,
+                row_spacing,
+                x_align: None,
+                y_align: None,
+            },
+        }
+    }
+    /// Creates a node that can be drawn (see [`draw`])
+    pub fn draw_with as draw(
+        drawable: impl Fn(Area<S>, &mut State, &mut Ctx) + MaybeSync + 'static,
+    ) {
+        NodeWith {
+            inner: NodeValue::Draw(Drawable::new(move |area, _, _, a, b| drawable(area, a, b))),
+        }
+    }
+    /// Creates a node that can be drawn, which sizes itself using `measure` (see [`draw_measured`])
+    pub fn draw_measured_with as draw_measured(
+        measure: impl Fn(ProposedSize<S>, &mut State, &mut Ctx) -> (S, S) + MaybeSync + 'static,
+        drawable: impl Fn(Area<S>, &mut State, &mut Ctx) + MaybeSync + 'static,
+    ) {
+        NodeWith {
+            inner: NodeValue::Explicit {
+                options: Size {
+                    measure: Some(Shared::new(measure)),
+                    ..Default::default()
+                },
+                element: Box::new(NodeCache::new(Self::draw(drawable).inner)),
+            },
+        }
+    }
+    /// Creates a node that displays text, sized by the [`TextMeasurer`] implemented by `Ctx`.
+    ///
+    /// The text is wrapped to the width available to the node, & the node is as tall as the wrapped text.
+    /// `drawable` should draw the text, wrapped to the width of its area.
+    pub fn text(
+        text: impl Into<String>,
+        style: Ctx::Style,
+        drawable: impl Fn(Area<S>, &mut State, &mut Ctx) + MaybeSync + 'static,
+    ) where [
+        Ctx: TextMeasurer,
+        Ctx::Style: MaybeSync + 'static,
+    ] {
+        let text = text.into();
+        Self::draw_measured(
+            move |proposed, _, ctx: &mut Ctx| {
+                let max_width = proposed.width.map(|width| width.to_f64() as f32);
+                let (width, height) = ctx.measure_text(&text, &style, max_width);
+                (S::from_f64(width as f64), S::from_f64(height as f64))
+            },
+            drawable,
+        )
+    }
+    /// Creates a node that can be drawn, which also receives the visible area it's clipped to (see [`draw_clipped`])
+    pub fn draw_clipped_with as draw_clipped(
+        drawable: impl Fn(Area<S>, Option<Area<S>>, &mut State, &mut Ctx) + MaybeSync + 'static,
+    ) {
+        NodeWith {
+            inner: NodeValue::Draw(Drawable::new(move |area, clip, _, a, b| {
+                drawable(area, clip, a, b)
+            })),
+        }
+    }
+    /// Creates a node that can be drawn, which also receives the opacity it should be drawn with (see [`draw_faded`])
+    pub fn draw_faded_with as draw_faded(
+        drawable: impl Fn(Area<S>, f32, &mut State, &mut Ctx) + MaybeSync + 'static,
+    ) {
+        NodeWith {
+            inner: NodeValue::Draw(Drawable::new(move |area, _, opacity, a, b| {
+                drawable(area, opacity, a, b)
+            })),
+        }
+    }
+    /// Creates an empty space which is laid out the same as any other node.
+    ///
+    /// To add spacing between each item in a row or column you can also use
+    /// [`row_spaced`] & [`column_spaced`]
+    pub fn space() {
+        NodeWith {
+            inner: NodeValue::Space,
+        }
+    }
+    /// Nothing! This will not have any impact on layout - useful for conditionally
+    /// adding elements to a layout in the case where nothing should be added.
+    ///
+    /// Outside of a sequence, like at the root of a layout, it fills the available area & draws nothing.
+    pub fn empty() {
+        NodeWith {
+            inner: NodeValue::Empty,
+        }
+    }
+    /// Creates a node whose content can be larger than the node itself along `axis` (see [`scroll`])
+    pub fn scroll_with as scroll(
+        axis: ScrollAxis,
+        offset: impl Fn(ScrollInfo<S>, &mut State, &mut Ctx) -> (S, S) + MaybeSync + 'static,
+        element: NodeWith<State, Ctx, S>,
+    ) {
+        NodeWith {
+            inner: NodeValue::Scroll {
+                axis,
+                offset: Shared::new(offset),
+                viewport: Area::default(),
+                element: Box::new(NodeCache::new(element.inner)),
+            },
+        }
+    }
+    /// Creates a vertical sequence of elements which are only built when they're visible (see [`virtual_column`])
+    pub fn virtual_column_with as virtual_column(
+        count: usize,
+        item_height: S,
+        build: impl Fn(usize, &mut State, &mut Ctx) -> NodeWith<State, Ctx, S> + MaybeSync + 'static,
+    ) {
+        virtual_sequence(Orientation::Vertical, count, item_height, build)
+    }
+    /// Creates a horizontal sequence of elements which are only built when they're visible (see [`virtual_column`])
+    pub fn virtual_row_with as virtual_row(
+        count: usize,
+        item_width: S,
+        build: impl Fn(usize, &mut State, &mut Ctx) -> NodeWith<State, Ctx, S> + MaybeSync + 'static,
+    ) {
+        virtual_sequence(Orientation::Horizontal, count, item_width, build)
+    }
+    /// Return nodes based on available area
+    ///
+    /// This node comes with caveats! Constraints within an area reader **cannot** expand the area reader itself.
+    /// If it could - it would create cyclical dependency which may be impossible to resolve.
+    pub fn area_reader_with as area_reader(
+        func: impl Fn(Area<S>, &mut State, &mut Ctx) -> NodeWith<State, Ctx, S> + MaybeSync + 'static,
+    ) {
+        NodeWith {
+            inner: NodeValue::AreaReader {
+                read: Shared::new(func),
+            },
+        }
     }
 }
+
 /// Creates a node that can be drawn
 /// This node is the point of integration with the UI library of your choice.
 /// ```rust
@@ -134,66 +414,171 @@ pub fn stack<State, Ctx>(elements: Vec<NodeWith<State, Ctx>>) -> NodeWith<State,
 ///  })
 ///}
 /// ```
-pub fn draw<State>(drawable: impl Fn(Area, &mut State) + 'static) -> Node<State> {
-    NodeWith {
-        inner: NodeValue::Draw(Drawable {
-            area: Area::default(),
-            draw: Rc::new(move |area, a, _| drawable(area, a)),
-        }),
-    }
+pub fn draw<State>(drawable: impl Fn(Area, &mut State) + MaybeSync + 'static) -> Node<State> {
+    NodeWith::draw(move |area, state, _| drawable(area, state))
 }
-/// Creates a node that can be drawn (see [`draw`])
-pub fn draw_with<State, Ctx>(
-    drawable: impl Fn(Area, &mut State, &mut Ctx) + 'static,
-) -> NodeWith<State, Ctx> {
-    NodeWith {
-        inner: NodeValue::Draw(Drawable {
-            area: Area::default(),
-            draw: Rc::new(move |area, a, b| drawable(area, a, b)),
-        }),
-    }
+/// Creates a node that can be drawn (see [`draw`]), which sizes itself using `measure`.
+///
+/// `measure` returns the `(width, height)` the node would like to be, given a [`ProposedSize`].
+/// The node is asked for its min-content width (a proposed width of `0.`), its max-content width (an unspecified width),
+/// & the height it needs to fit the width available to it.
+///
+/// This is primarily for content like text, images & native widgets, which know how big they should be.
+/// ```rust
+/// use backer::*;
+/// use backer::models::*;
+/// use backer::nodes::*;
+///
+/// // Text made of 10 words, each 10 wide & 5 tall, wrapped to the proposed width
+/// fn measure_text(proposed: ProposedSize, _: &mut ()) -> (f32, f32) {
+///     let words_per_line = (proposed.width.unwrap_or(f32::INFINITY) / 10.).floor().clamp(1., 10.);
+///     (words_per_line * 10., (10. / words_per_line).ceil() * 5.)
+/// }
+/// Layout::new(|()| {
+///     row(vec![
+///         draw_measured(measure_text, |area, _| {
+///             assert_eq!(area, Area::new(0., 47.5, 40., 15.));
+///         }),
+///         draw(|_, _| {}).width(60.),
+///     ])
+/// })
+/// .draw(Area::new(0., 0., 100., 110.), &mut ());
+/// ```
+pub fn draw_measured<State>(
+    measure: impl Fn(ProposedSize, &mut State) -> (f32, f32) + MaybeSync + 'static,
+    drawable: impl Fn(Area, &mut State) + MaybeSync + 'static,
+) -> Node<State> {
+    NodeWith::draw_measured(
+        move |proposed, state, _| measure(proposed, state),
+        move |area, state, _| drawable(area, state),
+    )
 }
-/// Creates an empty space which is laid out the same as any other node.
+/// Creates a node that can be drawn (see [`draw`]), which also receives the visible area it's clipped to.
 ///
-/// To add spacing between each item in a row or column you can also use
-/// [`row_spaced`] & [`column_spaced`]
-pub fn space<State, Ctx>() -> NodeWith<State, Ctx> {
-    NodeWith {
-        inner: NodeValue::Space,
-    }
+/// The clip area is the intersection of the areas of every clipping ancestor - [`scroll`] nodes
+/// & nodes clipped with [`NodeWith::clip`] - or `None` if there are no clipping ancestors.
+/// Nodes that are completely outside of their clip area aren't drawn.
+/// ```rust
+/// use backer::models::*;
+/// use backer::nodes::*;
+///
+/// scroll(
+///     ScrollAxis::Vertical,
+///     |_, _: &mut ()| (0., 0.),
+///     draw_clipped(|area: Area, clip: Option<Area>, _| {
+///         // Restrict drawing to `clip`, for example with a scissor rect
+///     })
+///     .height(1000.),
+/// );
+/// ```
+pub fn draw_clipped<State>(
+    drawable: impl Fn(Area, Option<Area>, &mut State) + MaybeSync + 'static,
+) -> Node<State> {
+    NodeWith::draw_clipped(move |area, clip, state, _| drawable(area, clip, state))
 }
-/// Nothing! This will not have any impact on layout - useful for conditionally
-/// adding elements to a layout in the case where nothing should be added.
-pub fn empty<State, Ctx>() -> NodeWith<State, Ctx> {
-    NodeWith {
-        inner: NodeValue::Empty,
-    }
+/// Creates a node that can be drawn (see [`draw`]), which also receives the opacity it should be drawn with.
+///
+/// The opacity is 1 unless the node or an ancestor is fading in or out with a [`NodeWith::transition`].
+/// ```rust
+/// use backer::models::*;
+/// use backer::nodes::*;
+///
+/// draw_faded(|area: Area, opacity: f32, _: &mut ()| {
+///     // Draw with `opacity`, for example by multiplying it into the alpha of a color
+/// });
+/// ```
+pub fn draw_faded<State>(
+    drawable: impl Fn(Area, f32, &mut State) + MaybeSync + 'static,
+) -> Node<State> {
+    NodeWith::draw_faded(move |area, opacity, state, _| drawable(area, opacity, state))
 }
-/// Return nodes based on available area
+/// Creates a node whose content can be larger than the node itself along `axis`.
 ///
-/// This node comes with caveats! Constraints within an area reader **cannot** expand the area reader itself.
-/// If it could - it would create cyclical dependency which may be impossible to resolve.
-pub fn area_reader<State>(
-    func: impl Fn(Area, &mut State, &mut ()) -> Node<State> + 'static,
+/// The content is laid out as large as its constraints require along the scroll axis,
+/// & at least as large as the scroll node. The scroll node itself has no constraints, like [`space`].
+///
+/// `offset` returns the scroll position as `(x, y)` from your state,
+/// given the size of the viewport & content (see [`ScrollInfo`]) - which can also be stored for drawing scrollbars.
+/// The offset is clamped to keep the content filling the viewport, & is ignored along axes that don't scroll.
+///
+/// Draw nodes within the content receive the viewport as their clip area (see [`draw_clipped`]).
+/// ```rust
+/// use backer::*;
+/// use backer::models::*;
+/// use backer::nodes::*;
+///
+/// struct MyState {
+///     scroll: f32,
+/// }
+/// let layout = Layout::new(|_: &mut MyState| {
+///     scroll(
+///         ScrollAxis::Vertical,
+///         |_, state: &mut MyState| (0., state.scroll),
+///         column((0..10).map(|_| draw(|_, _| {}).height(50.)).collect()),
+///     )
+///     .id("list")
+/// });
+/// let computed = layout.compute(Area::new(0., 0., 100., 100.), &mut MyState { scroll: 20. });
+/// let list = computed.root().find("list").unwrap();
+/// assert_eq!(list.area, Area::new(0., 0., 100., 100.));
+/// assert_eq!(list.children[0].children[0].area, Area::new(0., -20., 100., 500.));
+/// ```
+pub fn scroll<State, Ctx>(
+    axis: ScrollAxis,
+    offset: impl Fn(ScrollInfo, &mut State) -> (f32, f32) + MaybeSync + 'static,
+    element: NodeWith<State, Ctx>,
+) -> NodeWith<State, Ctx> {
+    NodeWith::scroll(axis, move |info, state, _| offset(info, state), element)
+}
+/// Creates a vertical sequence of `count` elements which are only built when they're visible.
+///
+/// Every element is `item_height` tall, so the sequence is `count * item_height` tall.
+/// Elements can also be measured individually with [`NodeWith::item_extents`].
+/// `build` is called with the index of each element inside the visible area, plus a few elements above & below
+/// (see [`NodeWith::overscan`]). The visible area is the layout's area, or the viewport of an enclosing [`scroll`] node.
+///
+/// Elements built as [`empty`] are skipped.
+/// ```rust
+/// use backer::*;
+/// use backer::models::*;
+/// use backer::nodes::*;
+///
+/// let computed = Layout::new(|_: &mut ()| {
+///     scroll(
+///         ScrollAxis::Vertical,
+///         |_, _| (0., 1000.),
+///         virtual_column(100_000, 20., |i, _| draw(|_, _| {}).id(i)),
+///     )
+/// })
+/// .compute(Area::new(0., 0., 100., 100.), &mut ());
+/// assert_eq!(computed.area(50_usize), Some(Area::new(0., 0., 100., 20.)));
+/// assert_eq!(computed.area(1000_usize), None);
+/// ```
+pub fn virtual_column<State>(
+    count: usize,
+    item_height: f32,
+    build: impl Fn(usize, &mut State) -> Node<State> + MaybeSync + 'static,
 ) -> Node<State> {
-    NodeWith {
-        inner: NodeValue::AreaReader {
-            read: Rc::new(func),
-        },
-    }
+    NodeWith::virtual_column(count, item_height, move |i, state, _| build(i, state))
+}
+/// Creates a horizontal sequence of `count` elements which are only built when they're visible.
+///
+/// Every element is `item_width` wide (see [`virtual_column`]).
+pub fn virtual_row<State>(
+    count: usize,
+    item_width: f32,
+    build: impl Fn(usize, &mut State) -> Node<State> + MaybeSync + 'static,
+) -> Node<State> {
+    NodeWith::virtual_row(count, item_width, move |i, state, _| build(i, state))
 }
 /// Return nodes based on available area
 ///
 /// This node comes with caveats! Constraints within an area reader **cannot** expand the area reader itself.
 /// If it could - it would create cyclical dependency which may be impossible to resolve.
-pub fn area_reader_with<State, Ctx>(
-    func: impl Fn(Area, &mut State, &mut Ctx) -> NodeWith<State, Ctx> + 'static,
-) -> NodeWith<State, Ctx> {
-    NodeWith {
-        inner: NodeValue::AreaReader {
-            read: Rc::new(func),
-        },
-    }
+pub fn area_reader<State>(
+    func: impl Fn(Area, &mut State, &mut ()) -> Node<State> + MaybeSync + 'static,
+) -> Node<State> {
+    NodeWith::area_reader(func)
 }
 /// Narrows or scopes the mutable state available to the children of this node
 /// The `StateScoper` generic must implement [`Scopable`] or [`ScopableOption`].
@@ -203,25 +588,14 @@ pub fn area_reader_with<State, Ctx>(
 ///
 /// The children of this node will only have access to the scoped state and context.
 pub fn scope<State, ScopedState, StateScoper>(
-    node: impl Fn(&mut ScopedState) -> Node<ScopedState> + 'static,
+    node: impl Fn(&mut ScopedState) -> Node<ScopedState> + MaybeSync + 'static,
 ) -> Node<State>
 where
     ScopedState: 'static,
     State: 'static,
     StateScoper: ScopableOption<State, ScopedState> + 'static,
 {
-    NodeWith {
-        inner: NodeValue::Scope {
-            scoped: Box::new(Subtree {
-                subtree_fn: Box::new(move |state, _| node(state)),
-                stored_tree: None,
-                _p: PhantomData,
-                _c: PhantomData,
-                _cs: PhantomData::<VoidScoper>,
-                _ss: PhantomData::<StateScoper>,
-            }),
-        },
-    }
+    NodeWith::scope::<ScopedState, (), StateScoper, VoidScoper>(move |state, _| node(state))
 }
 /// Narrows or scopes the mutable state available to the children of this node
 /// The `StateScoper` & CtxScoper generics both must implement [`Scopable`] or [`ScopableOption`].
@@ -231,7 +605,9 @@ where
 ///
 /// The children of this node will only have access to the scoped state and context.
 pub fn scope_with<State, ScopedState, Ctx, ScopedCtx, StateScoper, CtxScoper>(
-    node: impl Fn(&mut ScopedState, &mut ScopedCtx) -> NodeWith<ScopedState, ScopedCtx> + 'static,
+    node: impl Fn(&mut ScopedState, &mut ScopedCtx) -> NodeWith<ScopedState, ScopedCtx>
+        + MaybeSync
+        + 'static,
 ) -> NodeWith<State, Ctx>
 where
     ScopedState: 'static,
@@ -241,21 +617,80 @@ where
     StateScoper: ScopableOption<State, ScopedState> + 'static,
     CtxScoper: ScopableOption<Ctx, ScopedCtx> + 'static,
 {
+    NodeWith::scope::<ScopedState, ScopedCtx, StateScoper, CtxScoper>(node)
+}
+
+impl<State, Ctx, S: Scalar> NodeWith<State, Ctx, S> {
+    /// Narrows or scopes the mutable state & context available to the children of this node (see [`scope_with`])
+    pub fn scope<ScopedState, ScopedCtx, StateScoper, CtxScoper>(
+        node: impl Fn(&mut ScopedState, &mut ScopedCtx) -> NodeWith<ScopedState, ScopedCtx, S>
+            + MaybeSync
+            + 'static,
+    ) -> Self
+    where
+        ScopedState: 'static,
+        State: 'static,
+        ScopedCtx: 'static,
+        Ctx: 'static,
+        StateScoper: ScopableOption<State, ScopedState> + 'static,
+        CtxScoper: ScopableOption<Ctx, ScopedCtx> + 'static,
+    {
+        NodeWith {
+            inner: NodeValue::Scope {
+                scoped: Box::new(Subtree {
+                    subtree_fn: Box::new(node),
+                    stored_tree: None,
+                    _p: PhantomData,
+                    _c: PhantomData,
+                    _cs: PhantomData::<fn() -> CtxScoper>,
+                    _ss: PhantomData::<fn() -> StateScoper>,
+                }),
+            },
+        }
+    }
+}
+
+fn wrap<State, Ctx, S: Scalar>(
+    orientation: Orientation,
+    spacing: S,
+    line_spacing: S,
+    elements: Vec<NodeWith<State, Ctx, S>>,
+) -> NodeWith<State, Ctx, S> {
+    NodeWith {
+        inner: NodeValue::Wrap {
+            elements: filter_empty(ungroup(elements)),
+            orientation,
+            spacing,
+            line_spacing,
+            x_align: None,
+            y_align: None,
+        },
+    }
+}
+
+fn virtual_sequence<State, Ctx, S: Scalar>(
+    orientation: Orientation,
+    count: usize,
+    extent: S,
+    build: impl Fn(usize, &mut State, &mut Ctx) -> NodeWith<State, Ctx, S> + MaybeSync + 'static,
+) -> NodeWith<State, Ctx, S> {
     NodeWith {
-        inner: NodeValue::Scope {
-            scoped: Box::new(Subtree {
-                subtree_fn: Box::new(node),
-                stored_tree: None,
-                _p: PhantomData,
-                _c: PhantomData,
-                _cs: PhantomData::<CtxScoper>,
-                _ss: PhantomData::<StateScoper>,
-            }),
+        inner: NodeValue::Virtual {
+            orientation,
+            count,
+            // Elements without an extent are never visible, so none are built
+            extent: extent.max(S::ZERO),
+            extents: None,
+            overscan: 2,
+            build: Shared::new(build),
+            elements: Vec::new(),
         },
     }
 }
 
-fn ungroup<State, Ctx>(elements: Vec<NodeWith<State, Ctx>>) -> Vec<NodeCache<State, Ctx>> {
+fn ungroup<State, Ctx, S: Scalar>(
+    elements: Vec<NodeWith<State, Ctx, S>>,
+) -> Vec<NodeCache<State, Ctx, S>> {
     elements
         .into_iter()
         .flat_map(|el| {
@@ -271,7 +706,9 @@ fn ungroup<State, Ctx>(elements: Vec<NodeWith<State, Ctx>>) -> Vec<NodeCache<Sta
         .collect()
 }
 
-fn filter_empty<State, Ctx>(elements: Vec<NodeCache<State, Ctx>>) -> Vec<NodeCache<State, Ctx>> {
+fn filter_empty<State, Ctx, S: Scalar>(
+    elements: Vec<NodeCache<State, Ctx, S>>,
+) -> Vec<NodeCache<State, Ctx, S>> {
     elements
         .into_iter()
         .filter(|el| {
@@ -0,0 +1,13 @@
+//! The `alloc` items in `std`'s prelude, so modules read the same with & without the `std` feature
+
+// Tests link `std`, whose inherent float methods are used instead
+#[cfg(not(feature = "std"))]
+#[cfg_attr(test, allow(unused_imports))]
+pub(crate) use crate::math::Float;
+pub(crate) use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
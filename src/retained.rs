@@ -0,0 +1,326 @@
+use crate::prelude::*;
+use crate::{
+    computed::StableHasher,
+    constraints::SizeConstraints,
+    layout::NodeValue,
+    models::{Area, Direction, Size, Track, XAlign, YAlign},
+    node_cache::NodeCache,
+    traits::Scalar,
+};
+use alloc::collections::BTreeMap;
+use core::{
+    hash::{Hash, Hasher},
+    mem::discriminant,
+};
+
+/// Identifies a child within its parent across frames
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum ChildKey {
+    Index(usize),
+    Id(u64),
+}
+
+/// The layout results of a node from a previous frame, kept by a retained [`crate::Layout`]
+#[derive(Debug, Clone)]
+pub(crate) struct RetainedNode<S> {
+    signature: Option<u64>,
+    constraints: Option<(Area<S>, SizeConstraints<S>)>,
+    layout_input: Option<LayoutInput<S>>,
+    layout_area: Option<Area<S>>,
+    draw_area: Option<Area<S>>,
+    children: Vec<(ChildKey, RetainedNode<S>)>,
+}
+
+pub(crate) type LayoutInput<S> = (Area<S>, Option<XAlign>, Option<YAlign>, Direction);
+
+impl<S: Scalar> RetainedNode<S> {
+    pub(crate) fn snapshot<State, Ctx>(node: &NodeCache<State, Ctx, S>) -> Self {
+        RetainedNode {
+            signature: node.kind.signature(),
+            constraints: node.cached_constraints(),
+            layout_input: node.layout_input,
+            layout_area: node.layout_area,
+            draw_area: if let NodeValue::Draw(drawable) = &node.kind {
+                Some(drawable.area)
+            } else {
+                None
+            },
+            children: node
+                .kind
+                .children()
+                .into_iter()
+                .enumerate()
+                .map(|(i, child)| (child.kind.key(i), RetainedNode::snapshot(child)))
+                .collect(),
+        }
+    }
+}
+
+/// Compares a newly built tree against the previous frame's results.
+///
+/// Nodes whose parameters & children are unchanged are marked clean & reuse the previous frame's
+/// constraints & areas. Nodes with closures that affect layout are never clean.
+pub(crate) fn reconcile<State, Ctx, S: Scalar>(
+    node: &mut NodeCache<State, Ctx, S>,
+    previous: Option<&RetainedNode<S>>,
+) -> bool {
+    let signature = node.kind.signature();
+    let mut children = node.kind.children_mut();
+    let mut clean = signature.is_some()
+        && previous
+            .map(|previous| {
+                previous.signature == signature && previous.children.len() == children.len()
+            })
+            .unwrap_or(false);
+    // Children are matched by id where possible, so identified nodes keep their results when reordered
+    let mut previous_indices = BTreeMap::new();
+    for (index, (key, _)) in previous
+        .into_iter()
+        .flat_map(|previous| &previous.children)
+        .enumerate()
+    {
+        previous_indices.entry(*key).or_insert(index);
+    }
+    for (i, child) in children.iter_mut().enumerate() {
+        let key = child.kind.key(i);
+        let previous_child = previous
+            .zip(previous_indices.get(&key))
+            .map(|(previous, index)| (*index, &previous.children[*index]));
+        // The parent is only clean if its children are in the same order
+        clean &= previous_child.map(|(index, _)| index == i).unwrap_or(false);
+        clean &= reconcile(child, previous_child.map(|(_, (_, node))| node));
+    }
+    if let (true, Some(previous)) = (clean, previous) {
+        node.restore(
+            previous.constraints,
+            previous.layout_input,
+            previous.layout_area,
+        );
+        if let (NodeValue::Draw(drawable), Some(area)) = (&mut node.kind, previous.draw_area) {
+            drawable.area = area;
+        }
+    }
+    node.clean = clean;
+    clean
+}
+
+impl<State, Ctx, S: Scalar> NodeValue<State, Ctx, S> {
+    pub(crate) fn key(&self, index: usize) -> ChildKey {
+        match self {
+            NodeValue::Identified { id, .. }
+            | NodeValue::Animated { id: Some(id), .. }
+            | NodeValue::Transition { id: Some(id), .. } => ChildKey::Id(*id),
+            _ => ChildKey::Index(index),
+        }
+    }
+
+    pub(crate) fn children(&self) -> Vec<&NodeCache<State, Ctx, S>> {
+        match self {
+            NodeValue::Padding { element, .. }
+            | NodeValue::Explicit { element, .. }
+            | NodeValue::Identified { element, .. }
+            | NodeValue::Clip { element }
+            | NodeValue::Direction { element, .. }
+            | NodeValue::Animated { element, .. }
+            | NodeValue::Transition { element, .. }
+            | NodeValue::Offset { element, .. }
+            | NodeValue::Scroll { element, .. } => vec![element],
+            NodeValue::Column { elements, .. }
+            | NodeValue::Row { elements, .. }
+            | NodeValue::Stack { elements, .. }
+            | NodeValue::Wrap { elements, .. }
+            | NodeValue::Grid { elements, .. }
+            | NodeValue::Virtual { elements, .. }
+            | NodeValue::Group(elements) => elements.iter().collect(),
+            NodeValue::Coupled {
+                element, coupled, ..
+            } => vec![element, coupled],
+            NodeValue::Draw(_)
+            | NodeValue::Empty
+            | NodeValue::Space
+            | NodeValue::Scope { .. }
+            | NodeValue::AreaReader { .. } => Vec::new(),
+        }
+    }
+
+    pub(crate) fn children_mut(&mut self) -> Vec<&mut NodeCache<State, Ctx, S>> {
+        match self {
+            NodeValue::Padding { element, .. }
+            | NodeValue::Explicit { element, .. }
+            | NodeValue::Identified { element, .. }
+            | NodeValue::Clip { element }
+            | NodeValue::Direction { element, .. }
+            | NodeValue::Animated { element, .. }
+            | NodeValue::Transition { element, .. }
+            | NodeValue::Offset { element, .. }
+            | NodeValue::Scroll { element, .. } => vec![element],
+            NodeValue::Column { elements, .. }
+            | NodeValue::Row { elements, .. }
+            | NodeValue::Stack { elements, .. }
+            | NodeValue::Wrap { elements, .. }
+            | NodeValue::Grid { elements, .. }
+            | NodeValue::Virtual { elements, .. }
+            | NodeValue::Group(elements) => elements.iter_mut().collect(),
+            NodeValue::Coupled {
+                element, coupled, ..
+            } => vec![element, coupled],
+            NodeValue::Draw(_)
+            | NodeValue::Empty
+            | NodeValue::Space
+            | NodeValue::Scope { .. }
+            | NodeValue::AreaReader { .. } => Vec::new(),
+        }
+    }
+
+    /// A hash of every parameter of this node that affects layout, excluding its children.
+    ///
+    /// Returns `None` if the node's layout depends on closures, which can't be compared between frames.
+    fn signature(&self) -> Option<u64> {
+        let mut hasher = StableHasher::default();
+        discriminant(self).hash(&mut hasher);
+        let h = &mut hasher;
+        match self {
+            NodeValue::Padding { amounts, .. } => {
+                hash_scalars(
+                    h,
+                    &[
+                        amounts.leading,
+                        amounts.trailing,
+                        amounts.top,
+                        amounts.bottom,
+                    ],
+                );
+            }
+            NodeValue::Column {
+                spacing,
+                align,
+                off_axis_align,
+                distribution,
+                ..
+            } => {
+                hash_scalars(h, &[*spacing]);
+                (align, off_axis_align, distribution).hash(h);
+            }
+            NodeValue::Row {
+                spacing,
+                align,
+                off_axis_align,
+                distribution,
+                ..
+            } => {
+                hash_scalars(h, &[*spacing]);
+                (align, off_axis_align, distribution).hash(h);
+            }
+            NodeValue::Stack {
+                x_align, y_align, ..
+            } => (x_align, y_align).hash(h),
+            NodeValue::Wrap {
+                orientation,
+                spacing,
+                line_spacing,
+                x_align,
+                y_align,
+                ..
+            } => {
+                hash_scalars(h, &[*spacing, *line_spacing]);
+                (orientation, x_align, y_align).hash(h);
+            }
+            NodeValue::Grid {
+                columns,
+                rows,
+                column_spacing,
+                row_spacing,
+                x_align,
+                y_align,
+                ..
+            } => {
+                hash_tracks(h, columns);
+                hash_tracks(h, rows);
+                hash_scalars(h, &[*column_spacing, *row_spacing]);
+                (x_align, y_align).hash(h);
+            }
+            NodeValue::Identified { id, .. } => id.hash(h),
+            NodeValue::Direction { direction, .. } => direction.hash(h),
+            NodeValue::Animated {
+                id,
+                duration,
+                easing,
+                ..
+            } => (id, duration, easing).hash(h),
+            NodeValue::Transition { id, transition, .. } => (id, transition).hash(h),
+            NodeValue::Offset {
+                offset_x, offset_y, ..
+            } => hash_scalars(h, &[*offset_x, *offset_y]),
+            NodeValue::Explicit { options, .. } => hash_size(h, options)?,
+            NodeValue::Coupled { over, .. } => over.hash(h),
+            NodeValue::Group(_)
+            | NodeValue::Draw(_)
+            | NodeValue::Empty
+            | NodeValue::Space
+            | NodeValue::Clip { .. } => (),
+            NodeValue::Scope { .. }
+            | NodeValue::AreaReader { .. }
+            | NodeValue::Scroll { .. }
+            | NodeValue::Virtual { .. } => return None,
+        }
+        Some(hasher.finish())
+    }
+}
+
+fn hash_scalars<S: Scalar>(hasher: &mut impl Hasher, values: &[S]) {
+    values
+        .iter()
+        .for_each(|value| hasher.write_u64(value.to_bits()));
+}
+
+fn hash_optional_scalars<S: Scalar>(hasher: &mut impl Hasher, values: &[Option<S>]) {
+    values.iter().for_each(|value| {
+        value.is_some().hash(hasher);
+        hash_scalars(hasher, &[value.unwrap_or_default()]);
+    });
+}
+
+fn hash_tracks<S: Scalar>(hasher: &mut impl Hasher, tracks: &[Track<S>]) {
+    tracks.len().hash(hasher);
+    tracks.iter().for_each(|track| match track {
+        Track::Fixed(size) => {
+            0.hash(hasher);
+            hash_scalars(hasher, &[*size]);
+        }
+        Track::Range { min, max } => {
+            1.hash(hasher);
+            hash_optional_scalars(hasher, &[*min, *max]);
+        }
+        Track::Fraction(fraction) => {
+            2.hash(hasher);
+            hash_scalars(hasher, &[*fraction]);
+        }
+    });
+}
+
+fn hash_size<A, B, S: Scalar>(hasher: &mut impl Hasher, size: &Size<A, B, S>) -> Option<()> {
+    if size.dynamic_height.is_some() || size.dynamic_width.is_some() || size.measure.is_some() {
+        return None;
+    }
+    hash_optional_scalars(
+        hasher,
+        &[
+            size.width_min,
+            size.width_max,
+            size.height_min,
+            size.height_max,
+            size.aspect,
+            size.weight,
+        ],
+    );
+    (
+        size.x_align,
+        size.y_align,
+        size.expand_x,
+        size.expand_y,
+        size.column_span,
+        size.row_span,
+    )
+        .hash(hasher);
+    Some(())
+}
@@ -0,0 +1,50 @@
+//! Closure types that are reference counted with `Rc`, or with `Arc` & `Send + Sync` when the `sync` feature is enabled
+
+#[cfg(not(feature = "sync"))]
+pub(crate) use alloc::rc::Rc as Shared;
+#[cfg(feature = "sync")]
+pub(crate) use alloc::sync::Arc as Shared;
+
+/// A reference counted closure, like `Rc<dyn Fn(..)>`
+#[cfg(not(feature = "sync"))]
+macro_rules! shared_fn {
+    ($($signature:tt)*) => { alloc::rc::Rc<dyn $($signature)*> };
+}
+/// A reference counted closure, like `Arc<dyn Fn(..) + Send + Sync>`
+#[cfg(feature = "sync")]
+macro_rules! shared_fn {
+    ($($signature:tt)*) => { alloc::sync::Arc<dyn $($signature)* + Send + Sync> };
+}
+
+/// A boxed closure, like `Box<dyn Fn(..)>`
+#[cfg(not(feature = "sync"))]
+macro_rules! boxed_fn {
+    ($($signature:tt)*) => { alloc::boxed::Box<dyn $($signature)*> };
+}
+/// A boxed closure, like `Box<dyn Fn(..) + Send + Sync>`
+#[cfg(feature = "sync")]
+macro_rules! boxed_fn {
+    ($($signature:tt)*) => { alloc::boxed::Box<dyn $($signature)* + Send + Sync> };
+}
+
+pub(crate) use boxed_fn;
+pub(crate) use shared_fn;
+
+/// The state a [`crate::Layout`] keeps between frames, which is changed through `&Layout`.
+/// This is a `Mutex` when the `sync` feature is enabled, so layouts can be shared between threads.
+#[cfg(not(feature = "sync"))]
+pub(crate) type Lock<T> = core::cell::RefCell<T>;
+/// The state a [`crate::Layout`] keeps between frames, which is changed through `&Layout`.
+/// This is a `Mutex` when the `sync` feature is enabled, so layouts can be shared between threads.
+#[cfg(feature = "sync")]
+pub(crate) type Lock<T> = std::sync::Mutex<T>;
+
+#[cfg(not(feature = "sync"))]
+pub(crate) fn lock<T>(lock: &Lock<T>) -> core::cell::RefMut<'_, T> {
+    lock.borrow_mut()
+}
+#[cfg(feature = "sync")]
+pub(crate) fn lock<T>(lock: &Lock<T>) -> std::sync::MutexGuard<'_, T> {
+    // The state is only locked while backer updates it, which doesn't call any closures that could panic
+    lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
@@ -1,17 +1,21 @@
+use crate::prelude::*;
+use alloc::collections::BTreeSet;
 use core::fmt;
-use std::{
+use core::{
     fmt::{Debug, Formatter},
     marker::PhantomData,
 };
 
 use crate::{
-    models::{Area, XAlign, YAlign},
-    traits::{NodeTrait, ScopableOption},
+    animation::Animator,
+    models::{Area, ComputedNode, ConstraintConflict, DebugNode, Direction, XAlign, YAlign},
+    shared::boxed_fn,
+    traits::{NodeTrait, Scalar, ScopableOption},
     NodeWith,
 };
 
-type SubtreeFn<SubState, SubCtx> =
-    Box<dyn Fn(&mut SubState, &mut SubCtx) -> NodeWith<SubState, SubCtx>>;
+type SubtreeFn<SubState, SubCtx, S> =
+    boxed_fn!(Fn(&mut SubState, &mut SubCtx) -> NodeWith<SubState, SubCtx, S>);
 
 pub(crate) struct Subtree<
     SubState,
@@ -20,13 +24,15 @@ pub(crate) struct Subtree<
     Ctx,
     StateScoper: ScopableOption<State, SubState>,
     CtxScoper: ScopableOption<Ctx, SubCtx>,
+    S,
 > {
-    pub(crate) subtree_fn: SubtreeFn<SubState, SubCtx>,
-    pub(crate) stored_tree: Option<NodeWith<SubState, SubCtx>>,
-    pub(crate) _p: PhantomData<State>,
-    pub(crate) _c: PhantomData<Ctx>,
-    pub(crate) _ss: PhantomData<StateScoper>,
-    pub(crate) _cs: PhantomData<CtxScoper>,
+    pub(crate) subtree_fn: SubtreeFn<SubState, SubCtx, S>,
+    pub(crate) stored_tree: Option<NodeWith<SubState, SubCtx, S>>,
+    // Function pointers are `Send + Sync` whatever they return, so subtrees are too
+    pub(crate) _p: PhantomData<fn() -> State>,
+    pub(crate) _c: PhantomData<fn() -> Ctx>,
+    pub(crate) _ss: PhantomData<fn() -> StateScoper>,
+    pub(crate) _cs: PhantomData<fn() -> CtxScoper>,
 }
 
 impl<
@@ -36,7 +42,8 @@ impl<
         Ctx,
         StateScoper: ScopableOption<State, SubState>,
         CtxScoper: ScopableOption<Ctx, SubCtx>,
-    > Debug for Subtree<SubState, SubCtx, State, Ctx, StateScoper, CtxScoper>
+        S: Scalar,
+    > Debug for Subtree<SubState, SubCtx, State, Ctx, StateScoper, CtxScoper, S>
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.debug_struct("Subtree")
@@ -46,13 +53,14 @@ impl<
     }
 }
 
-impl<SubCtx, SubState, State, Ctx, StateScoper, CtxScoper> NodeTrait<State, Ctx>
-    for Subtree<SubState, SubCtx, State, Ctx, StateScoper, CtxScoper>
+impl<SubCtx, SubState, State, Ctx, StateScoper, CtxScoper, S> NodeTrait<State, Ctx, S>
+    for Subtree<SubState, SubCtx, State, Ctx, StateScoper, CtxScoper, S>
 where
     StateScoper: ScopableOption<State, SubState>,
     CtxScoper: ScopableOption<Ctx, SubCtx>,
+    S: Scalar,
 {
-    fn draw(&mut self, state: &mut State, ctx: &mut Ctx) {
+    fn draw(&mut self, clip: Option<Area<S>>, state: &mut State, ctx: &mut Ctx) {
         StateScoper::scope_option(state, |state| {
             CtxScoper::scope_option(ctx, |ctx| {
                 let (Some(state), Some(ctx)) = (state, ctx) else {
@@ -62,17 +70,61 @@ where
                     .stored_tree
                     .take()
                     .unwrap_or((self.subtree_fn)(state, ctx));
-                subtree.inner.draw(state, ctx);
+                subtree.inner.draw(clip, state, ctx);
                 self.stored_tree = Some(subtree);
                 None::<()>
             })
         });
     }
+    fn computed(&self, area: Area<S>) -> Vec<ComputedNode<S>> {
+        self.stored_tree
+            .iter()
+            .map(|subtree| subtree.inner.computed(area))
+            .collect()
+    }
+    fn animate(&mut self, animator: &mut Animator<S>) {
+        if let Some(subtree) = &mut self.stored_tree {
+            subtree.inner.animate(animator);
+        }
+    }
+    fn map_areas(&mut self, f: &dyn Fn(Area<S>) -> Area<S>) {
+        if let Some(subtree) = &mut self.stored_tree {
+            subtree.inner.map_areas(f);
+        }
+    }
+    fn debug_nodes(&self, depth: usize, nodes: &mut Vec<DebugNode<S>>) {
+        if let Some(subtree) = &self.stored_tree {
+            subtree.inner.debug_children(depth, nodes);
+        }
+    }
+    fn conflicts(
+        &self,
+        path: &mut Vec<usize>,
+        id: Option<u64>,
+        conflicts: &mut Vec<ConstraintConflict<S>>,
+    ) {
+        if let Some(subtree) = &self.stored_tree {
+            subtree.inner.conflict_children(path, id, conflicts);
+        }
+    }
+    fn duplicate_id(&self) -> Option<u64> {
+        self.stored_tree
+            .as_ref()
+            .and_then(|subtree| subtree.inner.duplicate_id(&mut BTreeSet::new()))
+    }
+    fn fade(&mut self, opacity: f32) {
+        if let Some(subtree) = &mut self.stored_tree {
+            subtree.inner.fade(opacity);
+        }
+    }
     fn layout(
         &mut self,
-        available_area: Area,
+        available_area: Area<S>,
         contextual_x_align: Option<XAlign>,
         contextual_y_align: Option<YAlign>,
+        visible: Area<S>,
+        direction: Direction,
+        cells: bool,
         state: &mut State,
         ctx: &mut Ctx,
     ) {
@@ -89,6 +141,9 @@ where
                     available_area,
                     contextual_x_align,
                     contextual_y_align,
+                    visible,
+                    direction,
+                    cells,
                     state,
                     ctx,
                 );
@@ -99,10 +154,11 @@ where
     }
     fn constraints(
         &mut self,
-        area: Area,
+        area: Area<S>,
+        cells: bool,
         state: &mut State,
         ctx: &mut Ctx,
-    ) -> crate::constraints::SizeConstraints {
+    ) -> crate::constraints::SizeConstraints<S> {
         StateScoper::scope_option(state, |state| {
             CtxScoper::scope_option(ctx, |ctx| {
                 let (Some(state), Some(ctx)) = (state, ctx) else {
@@ -112,7 +168,7 @@ where
                     .stored_tree
                     .take()
                     .unwrap_or((self.subtree_fn)(state, ctx));
-                let result = subtree.inner.constraints(area, state, ctx);
+                let result = subtree.inner.constraints(area, cells, state, ctx);
                 self.stored_tree = Some(subtree);
                 Some(result)
             })
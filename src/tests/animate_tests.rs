@@ -0,0 +1,173 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+    use crate::Node;
+    use std::time::Duration;
+
+    struct State {
+        wide: bool,
+        drawn: Vec<Area>,
+    }
+
+    fn layout(state: &mut State) -> Node<State> {
+        row(vec![
+            draw(|area, state: &mut State| state.drawn.push(area))
+                .width(if state.wide { 60. } else { 20. })
+                .id("panel")
+                .animate(Duration::from_secs(1), Easing::Linear),
+            space(),
+        ])
+    }
+
+    fn frame(layout: &mut Layout<State, ()>, state: &mut State, millis: u64) -> Area {
+        layout.set_time(Duration::from_millis(millis));
+        state.drawn.clear();
+        layout.draw(Area::new(0., 0., 100., 100.), state);
+        state.drawn[0]
+    }
+
+    #[test]
+    fn test_animate_interpolates_between_frames() {
+        let mut layout = Layout::new(layout);
+        let mut state = State {
+            wide: false,
+            drawn: Vec::new(),
+        };
+        assert_eq!(
+            frame(&mut layout, &mut state, 0),
+            Area::new(0., 0., 20., 100.)
+        );
+        assert!(!layout.is_animating());
+        state.wide = true;
+        assert_eq!(
+            frame(&mut layout, &mut state, 0),
+            Area::new(0., 0., 20., 100.)
+        );
+        assert!(layout.is_animating());
+        assert_eq!(
+            frame(&mut layout, &mut state, 250),
+            Area::new(0., 0., 30., 100.)
+        );
+        assert_eq!(
+            frame(&mut layout, &mut state, 1000),
+            Area::new(0., 0., 60., 100.)
+        );
+        assert!(!layout.is_animating());
+    }
+
+    #[test]
+    fn test_animate_interrupted() {
+        let mut layout = Layout::new(layout);
+        let mut state = State {
+            wide: false,
+            drawn: Vec::new(),
+        };
+        frame(&mut layout, &mut state, 0);
+        state.wide = true;
+        frame(&mut layout, &mut state, 0);
+        assert_eq!(
+            frame(&mut layout, &mut state, 500),
+            Area::new(0., 0., 40., 100.)
+        );
+        state.wide = false;
+        // The reverse animation starts from the interrupted area
+        assert_eq!(
+            frame(&mut layout, &mut state, 500),
+            Area::new(0., 0., 40., 100.)
+        );
+        assert_eq!(
+            frame(&mut layout, &mut state, 1000),
+            Area::new(0., 0., 30., 100.)
+        );
+        assert_eq!(
+            frame(&mut layout, &mut state, 1500),
+            Area::new(0., 0., 20., 100.)
+        );
+    }
+
+    #[test]
+    fn test_animate_retained() {
+        let mut layout = Layout::new(layout).retained();
+        let mut state = State {
+            wide: false,
+            drawn: Vec::new(),
+        };
+        frame(&mut layout, &mut state, 0);
+        state.wide = true;
+        frame(&mut layout, &mut state, 0);
+        assert_eq!(
+            frame(&mut layout, &mut state, 500),
+            Area::new(0., 0., 40., 100.)
+        );
+        assert_eq!(
+            frame(&mut layout, &mut state, 750),
+            Area::new(0., 0., 50., 100.)
+        );
+    }
+
+    #[test]
+    fn test_animate_scales_children() {
+        let mut layout = Layout::new(|state: &mut State| {
+            row(vec![
+                draw(|area, state: &mut State| state.drawn.push(area)),
+                draw(|area, state: &mut State| state.drawn.push(area)),
+            ])
+            .width(if state.wide { 80. } else { 40. })
+            .height(20.)
+            .id("row")
+            .animate(Duration::from_secs(1), Easing::Linear)
+        });
+        let mut state = State {
+            wide: false,
+            drawn: Vec::new(),
+        };
+        frame(&mut layout, &mut state, 0);
+        state.wide = true;
+        frame(&mut layout, &mut state, 0);
+        frame(&mut layout, &mut state, 500);
+        // Row children are drawn in reverse order
+        assert_eq!(
+            state.drawn,
+            vec![Area::new(50., 40., 30., 20.), Area::new(20., 40., 30., 20.)]
+        );
+        let computed = layout.compute(Area::new(0., 0., 100., 100.), &mut state);
+        assert_eq!(computed.area("row"), Some(Area::new(20., 40., 60., 20.)));
+    }
+
+    #[test]
+    fn test_animate_through_modifiers() {
+        let mut layout = Layout::new(|state: &mut State| {
+            row(vec![
+                draw(|area, state: &mut State| state.drawn.push(area))
+                    .width(if state.wide { 30. } else { 10. })
+                    .id("panel")
+                    .pad(5.)
+                    .animate(Duration::from_secs(1), Easing::Linear),
+                space(),
+            ])
+        });
+        let mut state = State {
+            wide: false,
+            drawn: Vec::new(),
+        };
+        frame(&mut layout, &mut state, 0);
+        state.wide = true;
+        frame(&mut layout, &mut state, 0);
+        assert!(layout.is_animating());
+        assert_eq!(
+            frame(&mut layout, &mut state, 500),
+            Area::new(3.75, 5., 22.5, 90.)
+        );
+    }
+
+    #[test]
+    fn test_easing() {
+        assert_eq!(Easing::Linear.ease(0.25), 0.25);
+        assert_eq!(Easing::EaseIn.ease(0.5), 0.125);
+        assert_eq!(Easing::EaseOut.ease(0.5), 0.875);
+        assert_eq!(Easing::EaseInOut.ease(0.5), 0.5);
+        assert_eq!(Easing::EaseInOut.ease(2.), 1.);
+    }
+}
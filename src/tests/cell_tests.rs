@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+    use crate::Node;
+
+    fn draw_cells() -> Node<Vec<CellRect>> {
+        draw(|area: Area, drawn: &mut Vec<CellRect>| {
+            assert_eq!(Area::from(area.to_cells()), area);
+            drawn.push(area.to_cells());
+        })
+    }
+
+    #[test]
+    fn test_leftover_cells_in_order() {
+        let mut drawn = Vec::new();
+        Layout::new(|_: &mut Vec<CellRect>| row((0..4).map(|_| draw_cells()).collect()))
+            .cells()
+            .draw(Area::new(0., 0., 11., 2.), &mut drawn);
+        drawn.sort_by_key(|rect| rect.x);
+        assert_eq!(
+            drawn.iter().map(|rect| rect.width).collect::<Vec<_>>(),
+            vec![3, 3, 3, 2]
+        );
+
+        let mut drawn = Vec::new();
+        Layout::new(|_: &mut Vec<CellRect>| {
+            column(vec![
+                draw_cells().height(1.5),
+                draw_cells().height(2.75),
+                draw_cells(),
+            ])
+        })
+        .cells()
+        .draw(Area::new(0., 0., 2., 10.), &mut drawn);
+        drawn.sort_by_key(|rect| rect.y);
+        // The largest remainder gets the leftover cell
+        assert_eq!(
+            drawn.iter().map(|rect| rect.height).collect::<Vec<_>>(),
+            vec![1, 3, 6]
+        );
+    }
+
+    #[test]
+    fn test_sequences_tile_cells() {
+        for width in 1..40 {
+            let mut drawn = Vec::new();
+            Layout::new(|_: &mut Vec<CellRect>| {
+                row_spaced(
+                    1.,
+                    vec![
+                        draw_cells(),
+                        draw_cells().weight(2.),
+                        draw_cells(),
+                        draw_cells().width_range(3.0..),
+                        draw_cells(),
+                    ],
+                )
+            })
+            .cells()
+            .draw(Area::new(0., 0., width as f32, 1.), &mut drawn);
+            drawn.sort_by_key(|rect| rect.x);
+            // Empty areas aren't drawn
+            if drawn.len() < 5 {
+                continue;
+            }
+            for pair in drawn.windows(2) {
+                assert_eq!(pair[0].x + pair[0].width as i32 + 1, pair[1].x);
+            }
+            let last = drawn[4];
+            assert_eq!(last.x + last.width as i32, width);
+        }
+    }
+
+    #[test]
+    fn test_alignment_grid_wrap_in_cells() {
+        let mut drawn = Vec::new();
+        Layout::new(|_: &mut Vec<CellRect>| {
+            column(vec![
+                draw_cells().width(3.).height(1.),
+                grid(
+                    vec![Track::Fraction(1.); 3],
+                    vec![],
+                    vec![draw_cells(), draw_cells(), draw_cells()],
+                ),
+                row_wrap((0..3).map(|_| draw_cells().width(2.)).collect()).pad(0.5),
+            ])
+        })
+        .cells()
+        .draw(Area::new(0., 0., 10.3, 7.), &mut drawn);
+        drawn.sort_by_key(|rect| (rect.y, rect.x));
+        // Centered areas are rounded half a cell up
+        assert_eq!(drawn[0], CellRect::new(4, 0, 3, 1));
+        assert_eq!(drawn[1].width + drawn[2].width + drawn[3].width, 10);
+        assert_eq!(drawn.len(), 7);
+    }
+
+    #[test]
+    fn test_cell_buffer() {
+        let mut buffer = CellBuffer::new(6, 3);
+        buffer.fill(CellRect::new(-2, 2, 4, 4), '#');
+        buffer.print(CellRect::new(1, 0, 4, 2), "one two three");
+        buffer.set(10, 10, 'x');
+        assert_eq!(buffer.get(0, 2), Some('#'));
+        assert_eq!(buffer.get(6, 0), None);
+        assert_eq!(buffer.lines(), vec![" one", " two", "##"]);
+        assert_eq!(
+            Area::new(0.4, 1.6, 2.2, 0.3).to_cells(),
+            CellRect::new(0, 2, 3, 0)
+        );
+    }
+}
@@ -0,0 +1,152 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+    use crate::traits::Scopable;
+    use crate::Node;
+
+    type Draws = Vec<(&'static str, Area, Option<Area>)>;
+
+    fn item(name: &'static str) -> Node<Draws> {
+        draw_clipped(move |area, clip, draws: &mut Draws| draws.push((name, area, clip)))
+    }
+
+    #[test]
+    fn test_clip_nested() {
+        let mut draws = Draws::new();
+        Layout::new(|_: &mut Draws| {
+            stack(vec![
+                item("outside"),
+                stack(vec![stack(vec![
+                    item("inside").offset(-20., 30.),
+                    item("hidden").width(10.).offset_x(-30.),
+                ])
+                .clip()
+                .pad(10.)
+                .offset(30., 0.)])
+                .width(50.)
+                .clip(),
+            ])
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut draws);
+        assert_eq!(
+            draws,
+            vec![
+                ("outside", Area::new(0., 0., 100., 100.), None),
+                (
+                    "inside",
+                    Area::new(45., 40., 30., 80.),
+                    Some(Area::new(65., 10., 10., 80.))
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_clip_explicit() {
+        let mut draws = Draws::new();
+        Layout::new(|_: &mut Draws| {
+            row(vec![
+                item("a").width(20.).height(20.).clip(),
+                item("b")
+                    .attach_under(item("background").offset_x(-10.))
+                    .width(20.)
+                    .height(20.)
+                    .clip(),
+            ])
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut draws);
+        assert_eq!(
+            draws,
+            vec![
+                (
+                    "background",
+                    Area::new(40., 40., 20., 20.),
+                    Some(Area::new(50., 40., 20., 20.))
+                ),
+                (
+                    "b",
+                    Area::new(50., 40., 20., 20.),
+                    Some(Area::new(50., 40., 20., 20.))
+                ),
+                (
+                    "a",
+                    Area::new(30., 40., 20., 20.),
+                    Some(Area::new(30., 40., 20., 20.))
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_clip_culls_to_occupied_area() {
+        let mut built = Vec::new();
+        Layout::new(|_: &mut Vec<usize>| {
+            row(vec![
+                virtual_column(100, 10., |i, built: &mut Vec<usize>| {
+                    built.push(i);
+                    space()
+                })
+                .height(60.)
+                .offset_y(-50.)
+                .clip(),
+                space().height(100.),
+            ])
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut built);
+        // The offset items are clipped to 0 to 30, so only items 3 to 5 & the overscan are built
+        assert_eq!(built, vec![1, 2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn test_clip_scope() {
+        struct State {
+            draws: Draws,
+        }
+        struct DrawsScoper;
+        impl Scopable<State, Draws> for DrawsScoper {
+            fn scope<Result>(scoping: &mut State, f: impl FnOnce(&mut Draws) -> Result) -> Result {
+                f(&mut scoping.draws)
+            }
+        }
+        let mut state = State { draws: Vec::new() };
+        Layout::new(|_: &mut State| {
+            scope::<_, _, DrawsScoper>(|_: &mut Draws| item("scoped").pad(-10.))
+                .pad(20.)
+                .clip()
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut state);
+        assert_eq!(
+            state.draws,
+            vec![(
+                "scoped",
+                Area::new(10., 10., 80., 80.),
+                Some(Area::new(0., 0., 100., 100.))
+            )]
+        );
+    }
+
+    #[test]
+    fn test_clip_hit_test() {
+        let computed = Layout::new(|_: &mut ()| {
+            stack(vec![draw(|_, _| {})
+                .width(20.)
+                .height(20.)
+                .offset_x(-15.)
+                .id("child")])
+            .width(40.)
+            .height(40.)
+            .clip()
+            .id("clip")
+        })
+        .compute(Area::new(0., 0., 100., 100.), &mut ());
+        assert_eq!(computed.area("clip"), Some(Area::new(30., 30., 40., 40.)));
+        assert_eq!(computed.area("child"), Some(Area::new(25., 40., 20., 20.)));
+        assert!(computed.hit_test(27., 50.).is_empty());
+        let hits = computed.hit_test(35., 50.);
+        assert_eq!(hits.len(), 2);
+        assert!(hits[0].has_id("child"));
+        assert!(hits[1].has_id("clip"));
+    }
+}
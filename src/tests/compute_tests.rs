@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+    use crate::traits::Scopable;
+    #[test]
+    fn test_compute_tree() {
+        let mut computed = Layout::new(|_: &mut usize| {
+            column(vec![
+                draw(|_, draws: &mut usize| *draws += 1).height(10.),
+                row(vec![
+                    draw(|_, draws: &mut usize| *draws += 1).pad(5.),
+                    space(),
+                ]),
+            ])
+        })
+        .compute(Area::new(0., 0., 100., 100.), &mut 0);
+        let root = computed.root();
+        assert_eq!(root.kind, NodeKind::Column);
+        assert_eq!(root.area, Area::new(0., 0., 100., 100.));
+        assert_eq!(root.children[0].kind, NodeKind::Explicit);
+        assert_eq!(root.children[0].area, Area::new(0., 0., 100., 10.));
+        assert_eq!(root.children[0].children[0].kind, NodeKind::Draw);
+        assert_eq!(
+            root.children[0].children[0].area,
+            Area::new(0., 0., 100., 10.)
+        );
+        let row = &root.children[1];
+        assert_eq!(row.kind, NodeKind::Row);
+        assert_eq!(row.area, Area::new(0., 10., 100., 90.));
+        assert_eq!(row.children[0].kind, NodeKind::Padding);
+        assert_eq!(row.children[0].area, Area::new(0., 10., 50., 90.));
+        assert_eq!(
+            row.children[0].children[0].area,
+            Area::new(5., 15., 40., 80.)
+        );
+        assert_eq!(row.children[1].kind, NodeKind::Space);
+
+        let mut draws = 0;
+        computed.draw(&mut draws);
+        assert_eq!(draws, 2);
+        computed.draw(&mut draws);
+        assert_eq!(draws, 4);
+    }
+    #[test]
+    fn test_compute_wrappers() {
+        let computed = Layout::new(|()| {
+            stack(vec![
+                draw(|_, _| {}).width(10.).height(10.).offset(5., 5.),
+                area_reader(|a, _, _| space().width(a.width * 0.5)),
+            ])
+        })
+        .compute(Area::new(0., 0., 100., 100.), &mut ());
+        let root = computed.root();
+        assert_eq!(root.children[0].kind, NodeKind::Offset);
+        assert_eq!(root.children[0].area, Area::new(50., 50., 10., 10.));
+        assert_eq!(root.children[1].kind, NodeKind::Explicit);
+        assert_eq!(root.children[1].area, Area::new(25., 0., 50., 100.));
+    }
+    #[test]
+    fn test_id_lookup() {
+        let computed = Layout::new(|()| {
+            row(vec![
+                draw(|a, _| {
+                    assert_eq!(a, Area::new(0., 40., 20., 20.));
+                })
+                .width(20.)
+                .height(20.)
+                .id("button"),
+                column(vec![space().id(1), space().id(2)]).id("list"),
+            ])
+            .align_contents(Align::Leading)
+            .expand()
+        })
+        .compute(Area::new(0., 0., 100., 100.), &mut ());
+        assert_eq!(computed.area("button"), Some(Area::new(0., 40., 20., 20.)));
+        assert_eq!(computed.area("list"), Some(Area::new(20., 0., 80., 100.)));
+        assert_eq!(computed.area(1), Some(Area::new(20., 0., 80., 50.)));
+        assert_eq!(computed.area(2), Some(Area::new(20., 50., 80., 50.)));
+        assert_eq!(computed.area(3), None);
+        assert_eq!(computed.root().find("list").unwrap().children.len(), 1);
+    }
+    #[test]
+    fn test_integer_ids_compare_by_value() {
+        let computed = Layout::new(|()| row(vec![space().id(1usize), space().id(2u8)]))
+            .compute(Area::new(0., 0., 100., 100.), &mut ());
+        assert_eq!(computed.area(1), Some(Area::new(0., 0., 50., 100.)));
+        assert_eq!(computed.area(2i64), Some(Area::new(50., 0., 50., 100.)));
+    }
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "use the id 0x")]
+    fn test_duplicate_id() {
+        Layout::new(|()| row(vec![space().id(1), space().id(1)]))
+            .compute(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_scoped_ids() {
+        struct Scoper;
+        impl Scopable<(), ()> for Scoper {
+            fn scope<Result>(scoping: &mut (), f: impl FnOnce(&mut ()) -> Result) -> Result {
+                f(scoping)
+            }
+        }
+        let computed = Layout::new(|()| {
+            row(vec![
+                space().id(1),
+                scope::<_, _, Scoper>(|()| space().id(1)),
+            ])
+        })
+        .compute(Area::new(0., 0., 100., 100.), &mut ());
+        assert_eq!(computed.area(1), Some(Area::new(0., 0., 50., 100.)));
+    }
+    #[test]
+    fn test_hit_test_paint_order() {
+        let ids = |computed: &ComputedLayout<(), ()>, x: f32, y: f32| -> Vec<u64> {
+            computed
+                .hit_test(x, y)
+                .into_iter()
+                .filter_map(|node| node.id)
+                .collect()
+        };
+        let id = |id: i32| crate::computed::hash_id(&id);
+
+        // Rows draw their contents in reverse
+        let computed = Layout::new(|()| row(vec![space().id(1).offset_x(30.), space().id(2)]))
+            .compute(Area::new(0., 0., 100., 100.), &mut ());
+        assert_eq!(ids(&computed, 60., 50.), vec![id(1), id(2)]);
+        assert_eq!(ids(&computed, 10., 50.), vec![]);
+
+        // Stacks draw their contents in order, children are drawn over their parents
+        let computed =
+            Layout::new(|()| stack(vec![space().id(1), column(vec![space().id(2)]).id(3)]))
+                .compute(Area::new(0., 0., 100., 100.), &mut ());
+        assert_eq!(ids(&computed, 50., 50.), vec![id(2), id(3), id(1)]);
+
+        // Attached nodes are drawn over or under the node they're attached to
+        let computed = Layout::new(|()| {
+            stack(vec![
+                space().id(1).attach_over(space().id(2)),
+                space().id(3).attach_under(space().id(4)),
+            ])
+        })
+        .compute(Area::new(0., 0., 100., 100.), &mut ());
+        assert_eq!(ids(&computed, 50., 50.), vec![id(3), id(4), id(2), id(1)]);
+    }
+}
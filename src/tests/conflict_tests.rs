@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use crate::computed::hash_id;
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_conflicts_resolved_to_min() {
+        let layout = Layout::new(|_: &mut ()| {
+            column(vec![
+                draw(|_, _| {}),
+                row(vec![draw(|area, _| {
+                    assert_eq!(area, Area::new(40., 70., 20., 30.))
+                })
+                .width_range(20.0..10.)
+                .height_range(30.0..=5.)])
+                .id("row"),
+            ])
+        });
+        let computed = layout.compute(Area::new(0., 0., 100., 100.), &mut ());
+        assert_eq!(
+            computed.conflicts(),
+            vec![
+                ConstraintConflict {
+                    path: vec![1, 0, 0],
+                    id: Some(hash_id(&"row")),
+                    dimension: Dimension::Width,
+                    min: 20.,
+                    max: 10.,
+                    resolved: 20.,
+                },
+                ConstraintConflict {
+                    path: vec![1, 0, 0],
+                    id: Some(hash_id(&"row")),
+                    dimension: Dimension::Height,
+                    min: 30.,
+                    max: 5.,
+                    resolved: 30.,
+                },
+            ]
+        );
+        layout.draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+
+    #[test]
+    fn test_conflict_hook() {
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let hook = reported.clone();
+        let layout = Layout::new(|_: &mut ()| draw(|_, _| {}).height_range(20.0..10.))
+            .on_conflict(move |conflict| hook.lock().unwrap().push(conflict.clone()));
+        layout.draw(Area::new(0., 0., 100., 100.), &mut ());
+        layout.draw(Area::new(0., 0., 100., 100.), &mut ());
+        let reported = reported.lock().unwrap();
+        assert_eq!(reported.len(), 2);
+        assert_eq!(reported[0].path, Vec::<usize>::new());
+        assert_eq!(reported[0].dimension, Dimension::Height);
+    }
+
+    #[test]
+    fn test_invalid_bounds_dont_panic() {
+        let computed = Layout::new(|_: &mut ()| {
+            column(vec![
+                draw(|_, _| {}).dynamic_height(|_, _| f32::NAN),
+                draw(|_, _| {}).height(f32::NAN),
+                grid(
+                    vec![Track::Range {
+                        min: Some(20.),
+                        max: Some(10.),
+                    }],
+                    vec![],
+                    vec![draw(|_, _| {}).id("cell")],
+                ),
+            ])
+        })
+        .compute(Area::new(0., 0., 100., 90.), &mut ());
+        assert!(computed.conflicts().is_empty());
+        assert_eq!(computed.area("cell").unwrap().width, 20.);
+    }
+}
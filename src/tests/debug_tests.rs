@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+    use crate::Node;
+
+    fn layout(_: &mut Vec<DebugNode>) -> Node<Vec<DebugNode>> {
+        row_spaced(
+            10.,
+            vec![
+                draw(|_, _| {}).width(20.).id("fixed"),
+                draw(|_, _| {}).pad_leading(5.).pad_top(10.),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_debug_nodes() {
+        let computed = Layout::new(layout).compute(Area::new(0., 0., 100., 100.), &mut Vec::new());
+        let nodes = computed.debug_nodes();
+        assert_eq!(
+            nodes
+                .iter()
+                .map(|node| (node.kind, node.depth))
+                .collect::<Vec<_>>(),
+            vec![
+                (NodeKind::Row, 0),
+                (NodeKind::Identified, 1),
+                (NodeKind::Explicit, 2),
+                (NodeKind::Draw, 3),
+                (NodeKind::Padding, 1),
+                (NodeKind::Padding, 2),
+                (NodeKind::Draw, 3),
+            ]
+        );
+        assert_eq!(nodes[0].spacing, vec![Area::new(20., 0., 10., 100.)]);
+        assert_eq!(nodes[1].area, Area::new(0., 0., 20., 100.));
+        assert_eq!(nodes[1].constraints.width_min, Some(20.));
+        assert_eq!(nodes[1].constraints.width_max, Some(20.));
+        assert_eq!(nodes[1].constraints.height_max, None);
+        assert_eq!(nodes[4].padding, vec![Area::new(30., 0., 70., 10.)]);
+        assert_eq!(nodes[5].padding, vec![Area::new(30., 10., 5., 90.)]);
+        assert_eq!(nodes[6].area, Area::new(35., 10., 65., 90.));
+    }
+
+    #[test]
+    fn test_debug_rtl_spacing() {
+        let computed = Layout::new(layout)
+            .direction(Direction::RightToLeft)
+            .compute(Area::new(0., 0., 100., 100.), &mut Vec::new());
+        let nodes = computed.debug_nodes();
+        assert_eq!(nodes[0].spacing, vec![Area::new(70., 0., 10., 100.)]);
+        assert_eq!(nodes[1].area, Area::new(80., 0., 20., 100.));
+    }
+
+    #[test]
+    fn test_debug_overlay() {
+        let mut layout = Layout::new(layout).debug(|node, nodes| nodes.push(node.clone()));
+        let mut nodes = Vec::new();
+        layout.draw(Area::new(0., 0., 100., 100.), &mut nodes);
+        assert_eq!(nodes.len(), 7);
+        layout.set_debugging(false);
+        nodes.clear();
+        layout.draw(Area::new(0., 0., 100., 100.), &mut nodes);
+        assert!(nodes.is_empty());
+    }
+}
@@ -0,0 +1,118 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+    use crate::Node;
+
+    fn layout(_: &mut ()) -> Node<()> {
+        column(vec![
+            row(vec![
+                draw(|_, _| {}).width(10.).id("first"),
+                draw(|_, _| {}).width(20.).id("second"),
+            ])
+            .align_contents(Align::Leading)
+            .expand_x()
+            .height(10.),
+            draw(|_, _| {})
+                .width(10.)
+                .height(10.)
+                .align(Align::Leading)
+                .id("leading"),
+            draw(|_, _| {})
+                .id("padded")
+                .pad_leading(10.)
+                .pad_trailing(20.),
+            grid(
+                vec![Track::Fixed(30.), Track::Fraction(1.)],
+                vec![],
+                vec![draw(|_, _| {}).id("cell 0"), draw(|_, _| {}).id("cell 1")],
+            )
+            .height(10.),
+            draw(|_, _| {})
+                .width(10.)
+                .height(10.)
+                .offset_x(5.)
+                .id("offset"),
+        ])
+    }
+
+    #[test]
+    fn test_direction_ltr() {
+        let computed = Layout::new(layout).compute(Area::new(0., 0., 100., 100.), &mut ());
+        assert_eq!(computed.area("first"), Some(Area::new(0., 0., 10., 10.)));
+        assert_eq!(computed.area("second"), Some(Area::new(10., 0., 20., 10.)));
+        assert_eq!(computed.area("leading"), Some(Area::new(0., 10., 10., 10.)));
+        assert_eq!(computed.area("padded"), Some(Area::new(10., 20., 70., 60.)));
+        assert_eq!(computed.area("cell 0"), Some(Area::new(0., 80., 30., 10.)));
+        assert_eq!(computed.area("cell 1"), Some(Area::new(30., 80., 70., 10.)));
+        assert_eq!(computed.area("offset"), Some(Area::new(50., 90., 10., 10.)));
+    }
+
+    #[test]
+    fn test_direction_rtl() {
+        let computed = Layout::new(layout)
+            .direction(Direction::RightToLeft)
+            .compute(Area::new(0., 0., 100., 100.), &mut ());
+        assert_eq!(computed.area("first"), Some(Area::new(90., 0., 10., 10.)));
+        assert_eq!(computed.area("second"), Some(Area::new(70., 0., 20., 10.)));
+        assert_eq!(
+            computed.area("leading"),
+            Some(Area::new(90., 10., 10., 10.))
+        );
+        assert_eq!(computed.area("padded"), Some(Area::new(20., 20., 70., 60.)));
+        assert_eq!(computed.area("cell 0"), Some(Area::new(70., 80., 30., 10.)));
+        assert_eq!(computed.area("cell 1"), Some(Area::new(0., 80., 70., 10.)));
+        // Offsets are absolute, so they move the node in the same direction
+        assert_eq!(computed.area("offset"), Some(Area::new(50., 90., 10., 10.)));
+    }
+
+    #[test]
+    fn test_direction_override() {
+        let computed = Layout::new(|_: &mut ()| {
+            row(vec![
+                draw(|_, _| {}).width(10.).id("a"),
+                row(vec![
+                    draw(|_, _| {}).width(10.).id("b"),
+                    draw(|_, _| {}).id("c"),
+                ])
+                .direction(Direction::LeftToRight),
+            ])
+        })
+        .direction(Direction::RightToLeft)
+        .compute(Area::new(0., 0., 100., 100.), &mut ());
+        assert_eq!(computed.area("a"), Some(Area::new(90., 0., 10., 100.)));
+        assert_eq!(computed.area("b"), Some(Area::new(0., 0., 10., 100.)));
+        assert_eq!(computed.area("c"), Some(Area::new(10., 0., 80., 100.)));
+    }
+
+    #[test]
+    fn test_direction_retained() {
+        let mut layout = Layout::new(layout).retained();
+        let area = Area::new(0., 0., 100., 100.);
+        layout.compute(area, &mut ());
+        layout.set_direction(Direction::RightToLeft);
+        let computed = layout.compute(area, &mut ());
+        assert_eq!(computed.area("first"), Some(Area::new(90., 0., 10., 10.)));
+        layout.set_direction(Direction::LeftToRight);
+        let computed = layout.compute(area, &mut ());
+        assert_eq!(computed.area("first"), Some(Area::new(0., 0., 10., 10.)));
+    }
+
+    #[test]
+    fn test_direction_virtual() {
+        let computed = Layout::new(|_: &mut ()| {
+            scroll(
+                ScrollAxis::Horizontal,
+                |_, _| (25., 0.),
+                virtual_row(100, 10., |i, _| draw(|_, _| {}).id(i)).overscan(0),
+            )
+        })
+        .direction(Direction::RightToLeft)
+        .compute(Area::new(0., 0., 30., 10.), &mut ());
+        assert_eq!(computed.area(0_usize), None);
+        assert_eq!(computed.area(2_usize), Some(Area::new(25., 0., 10., 10.)));
+        assert_eq!(computed.area(5_usize), Some(Area::new(-5., 0., 10., 10.)));
+        assert_eq!(computed.area(6_usize), None);
+    }
+}
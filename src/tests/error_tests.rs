@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod tests {
+    use crate::computed::hash_id;
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+
+    #[test]
+    fn test_empty_root_draws_nothing() {
+        let layout = Layout::new(|_: &mut ()| empty());
+        let computed = layout.compute(Area::new(0., 0., 100., 100.), &mut ());
+        assert_eq!(computed.root().kind, NodeKind::Empty);
+        assert_eq!(computed.root().area, Area::new(0., 0., 100., 100.));
+        Layout::new(|_: &mut ()| empty().pad(10.)).draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+
+    #[test]
+    fn test_group_root_stacks_elements() {
+        let mut count = 0;
+        Layout::new(|_: &mut usize| {
+            group(vec![
+                draw(|area, count: &mut usize| {
+                    assert_eq!(area, Area::new(0., 0., 100., 100.));
+                    *count += 1;
+                }),
+                draw(|area, count: &mut usize| {
+                    assert_eq!(area, Area::new(45., 0., 10., 100.));
+                    *count += 1;
+                })
+                .width(10.),
+            ])
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut count);
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn test_area_reader_returns_group_or_empty() {
+        let mut count = 0;
+        Layout::new(|_: &mut usize| {
+            column(vec![
+                area_reader(|_, _, _| empty()),
+                area_reader(|area, _, _| {
+                    group(vec![
+                        draw(move |drawn, count: &mut usize| {
+                            assert_eq!(drawn, area);
+                            *count += 1;
+                        }),
+                        empty(),
+                    ])
+                }),
+            ])
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut count);
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_try_draw_duplicate_id() {
+        let mut drawn = false;
+        let result = Layout::new(|_: &mut bool| {
+            row(vec![
+                draw(|_, drawn: &mut bool| *drawn = true).id(1),
+                draw(|_, drawn: &mut bool| *drawn = true).id(1),
+            ])
+        })
+        .try_draw(Area::new(0., 0., 100., 100.), &mut drawn);
+        assert_eq!(result, Err(LayoutError::DuplicateId(hash_id(&1))));
+        assert!(!drawn);
+    }
+
+    #[test]
+    fn test_duplicate_id_message() {
+        let result = Layout::new(|()| column(vec![space().id("header"), space().id("header")]))
+            .try_compute(Area::new(0., 0., 100., 100.), &mut ());
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            format!(
+                "Multiple nodes in the same scope use the id {:#018x}",
+                hash_id(&"header")
+            )
+        );
+    }
+
+    #[test]
+    fn test_duplicate_id_without_debug() {
+        #[derive(Hash)]
+        struct Key;
+        let result = Layout::new(|()| column(vec![space().id(Key), space().id(Key)]))
+            .try_compute(Area::new(0., 0., 100., 100.), &mut ());
+        assert_eq!(result.err(), Some(LayoutError::DuplicateId(hash_id(&Key))));
+    }
+
+    #[test]
+    fn test_try_draw_invalid_area() {
+        let layout = Layout::new(|_: &mut ()| draw(|_, _| {}));
+        for area in [
+            Area::new(f32::NAN, 0., 100., 100.),
+            Area::new(0., 0., f32::INFINITY, 100.),
+            Area::new(0., 0., 100., -1.),
+        ] {
+            assert!(matches!(
+                layout.try_draw(area, &mut ()),
+                Err(LayoutError::InvalidArea(_))
+            ));
+        }
+        assert_eq!(layout.try_draw(Area::new(0., 0., 0., 0.), &mut ()), Ok(()));
+    }
+}
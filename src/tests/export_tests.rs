@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod tests {
+    use crate::computed::hash_id;
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+
+    #[test]
+    fn test_export_json() {
+        let computed = Layout::new(|_: &mut ()| {
+            column(vec![draw(|_, _| {}).height(20.), draw(|_, _| {}).pad(-0.)])
+        })
+        .compute(Area::new(0., 0., 100., 50.5), &mut ());
+        assert_eq!(
+            computed.to_json(),
+            r#"{
+  "kind": "Column",
+  "id": null,
+  "area": { "x": 0, "y": 0, "width": 100, "height": 50.5 },
+  "constraints": { "width_min": null, "width_max": null, "height_min": 20, "height_max": null, "aspect": null, "expand_x": false, "expand_y": false },
+  "children": [
+    {
+      "kind": "Explicit",
+      "id": null,
+      "area": { "x": 0, "y": 0, "width": 100, "height": 20 },
+      "constraints": { "width_min": null, "width_max": null, "height_min": 20, "height_max": 20, "aspect": null, "expand_x": false, "expand_y": false },
+      "children": [
+        {
+          "kind": "Draw",
+          "id": null,
+          "area": { "x": 0, "y": 0, "width": 100, "height": 20 },
+          "constraints": { "width_min": null, "width_max": null, "height_min": null, "height_max": null, "aspect": null, "expand_x": false, "expand_y": false },
+          "children": []
+        }
+      ]
+    },
+    {
+      "kind": "Padding",
+      "id": null,
+      "area": { "x": 0, "y": 20, "width": 100, "height": 30.5 },
+      "constraints": { "width_min": null, "width_max": null, "height_min": null, "height_max": null, "aspect": null, "expand_x": false, "expand_y": false },
+      "children": [
+        {
+          "kind": "Draw",
+          "id": null,
+          "area": { "x": 0, "y": 20, "width": 100, "height": 30.5 },
+          "constraints": { "width_min": null, "width_max": null, "height_min": null, "height_max": null, "aspect": null, "expand_x": false, "expand_y": false },
+          "children": []
+        }
+      ]
+    }
+  ]
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_export_json_ids() {
+        let computed = Layout::new(|_: &mut ()| draw(|_, _| {}).id("node"))
+            .compute(Area::new(0., 0., 10., 10.), &mut ());
+        let id = format!(r#""id": "{:#018x}","#, hash_id(&"node"));
+        assert!(computed.to_json().contains(&id));
+    }
+
+    #[test]
+    fn test_export_ids_are_stable() {
+        // Ids in snapshots don't change between platforms or Rust releases
+        assert_eq!(hash_id(&"node"), 0xab09_8176_f008_4092);
+    }
+
+    #[test]
+    fn test_export_svg() {
+        let computed = Layout::new(|_: &mut ()| {
+            row(vec![
+                draw(|_, _| {}).width(20.),
+                space().attach_over(draw(|_, _| {})),
+            ])
+        })
+        .compute(Area::new(0., 0., 100., 50.), &mut ());
+        let svg = computed.to_svg();
+        assert!(svg.starts_with(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 100 50" width="100" height="50">"#
+        ));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(svg.contains(
+            r##"<rect x="20" y="0" width="80" height="50" fill="none" fill-opacity="0.1" stroke="#3cb44b" stroke-width="1"/>"##
+        ));
+        assert!(svg.contains(
+            r##"<text x="22" y="9" font-family="monospace" font-size="8" fill="#3cb44b">CoupledOver</text>"##
+        ));
+        // Labels of nodes that share an origin don't overlap
+        assert!(svg.contains(
+            r##"<text x="22" y="18" font-family="monospace" font-size="8" fill="#4363d8">Space</text>"##
+        ));
+        assert_eq!(svg.matches("<rect").count(), computed.debug_nodes().len());
+    }
+}
@@ -0,0 +1,213 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+    #[test]
+    fn test_grid_basic() {
+        Layout::new(|()| {
+            grid(
+                vec![Track::Fixed(20.), Track::Fraction(1.)],
+                vec![],
+                vec![
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(0., 0., 20., 50.));
+                    }),
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(20., 0., 80., 50.));
+                    }),
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(0., 50., 20., 50.));
+                    }),
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(20., 50., 80., 50.));
+                    }),
+                ],
+            )
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_grid_fractions() {
+        Layout::new(|()| {
+            grid(
+                vec![Track::Fraction(1.), Track::Fraction(3.)],
+                vec![Track::Fixed(10.), Track::Fraction(1.)],
+                vec![
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(0., 0., 25., 10.));
+                    }),
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(25., 0., 75., 10.));
+                    }),
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(0., 10., 25., 90.));
+                    }),
+                ],
+            )
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_grid_content_sized_columns() {
+        Layout::new(|()| {
+            grid(
+                vec![Track::Fraction(1.), Track::Fraction(1.)],
+                vec![],
+                vec![
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(0., 0., 30., 50.));
+                    })
+                    .width(30.),
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(30., 0., 70., 50.));
+                    }),
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(5., 50., 20., 50.));
+                    })
+                    .width(20.),
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(30., 50., 70., 50.));
+                    }),
+                ],
+            )
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+        Layout::new(|()| {
+            grid(
+                vec![Track::range(..40.), Track::range(10.0..)],
+                vec![],
+                vec![
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(0., 0., 40., 100.));
+                    }),
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(40., 0., 60., 100.));
+                    }),
+                ],
+            )
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_grid_span() {
+        Layout::new(|()| {
+            grid(
+                vec![Track::Fraction(1.), Track::Fraction(1.)],
+                vec![],
+                vec![
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(0., 0., 100., 50.));
+                    })
+                    .span(2, 1),
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(0., 50., 50., 50.));
+                    }),
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(50., 50., 50., 50.));
+                    }),
+                ],
+            )
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+        Layout::new(|()| {
+            grid_spaced(
+                10.,
+                10.,
+                vec![Track::Fraction(1.), Track::Fraction(1.)],
+                vec![],
+                vec![
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(0., 0., 45., 100.));
+                    })
+                    .span(1, 2),
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(55., 0., 45., 45.));
+                    }),
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(55., 55., 45., 45.));
+                    }),
+                ],
+            )
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_grid_span_padded() {
+        Layout::new(|()| {
+            grid(
+                vec![Track::Fixed(10.), Track::Fixed(10.)],
+                vec![],
+                vec![
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(42., 2., 16., 46.));
+                    })
+                    .span(2, 1)
+                    .pad(2.),
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(40., 50., 10., 50.));
+                    }),
+                ],
+            )
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_grid_span_padded_with_width() {
+        Layout::new(|()| {
+            grid(
+                vec![Track::Fixed(10.), Track::Fixed(10.)],
+                vec![],
+                vec![
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(46., 2., 8., 46.));
+                    })
+                    .span(2, 1)
+                    .pad(2.)
+                    .width(12.),
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(40., 50., 10., 50.));
+                    }),
+                ],
+            )
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_grid_constraints() {
+        Layout::new(|()| {
+            grid_spaced(
+                10.,
+                10.,
+                vec![Track::Fixed(20.), Track::Fixed(30.)],
+                vec![Track::Fixed(10.)],
+                vec![
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(20., 45., 20., 10.));
+                    }),
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(50., 45., 30., 10.));
+                    }),
+                ],
+            )
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+        Layout::new(|()| {
+            grid(
+                vec![Track::Fixed(20.), Track::Fixed(30.)],
+                vec![Track::Fixed(10.)],
+                vec![
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(0., 0., 20., 10.));
+                    }),
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(20., 0., 30., 10.));
+                    }),
+                ],
+            )
+            .align_contents(Align::TopLeading)
+            .expand()
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+}
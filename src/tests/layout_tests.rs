@@ -650,4 +650,25 @@ mod tests {
         })
         .draw(Area::new(0., 0., 100., 100.), &mut ());
     }
+
+    #[test]
+    fn test_explicit_update_keeps_height_max() {
+        // Adding a width to a node with a height range keeps its upper bound, & vice versa
+        Layout::new(|()| {
+            draw(|a, _| {
+                assert_eq!(a, Area::new(40., 25., 20., 50.));
+            })
+            .height_range(10.0..=50.)
+            .width(20.)
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+        Layout::new(|()| {
+            draw(|a, _| {
+                assert_eq!(a, Area::new(40., 25., 20., 50.));
+            })
+            .width(20.)
+            .height_range(10.0..=50.)
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
 }
@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+    use crate::Node;
+
+    #[derive(Default)]
+    struct State {
+        proposals: Vec<ProposedSize>,
+        draws: Vec<Area>,
+    }
+
+    /// Text made of 10 words, each 10 wide & 5 tall, wrapped to the proposed width
+    fn text() -> Node<State> {
+        draw_measured(
+            |proposed, state: &mut State| {
+                state.proposals.push(proposed);
+                let words_per_line = (proposed.width.unwrap_or(f32::INFINITY) / 10.)
+                    .floor()
+                    .clamp(1., 10.);
+                (words_per_line * 10., (10. / words_per_line).ceil() * 5.)
+            },
+            |area, state: &mut State| state.draws.push(area),
+        )
+    }
+
+    #[test]
+    fn test_measure_proposals() {
+        let mut state = State::default();
+        Layout::new(|_: &mut State| text()).draw(Area::new(0., 0., 50., 50.), &mut state);
+        assert_eq!(
+            state.proposals[..3],
+            [
+                ProposedSize {
+                    width: Some(0.),
+                    height: None
+                },
+                ProposedSize {
+                    width: None,
+                    height: None
+                },
+                ProposedSize {
+                    width: Some(50.),
+                    height: None
+                },
+            ]
+        );
+        assert_eq!(state.draws, vec![Area::new(0., 20., 50., 10.)]);
+    }
+
+    #[test]
+    fn test_measure_content_widths() {
+        let mut state = State::default();
+        Layout::new(|_: &mut State| row(vec![text(), draw(|_, _| {}).width_range(50.0..)]))
+            .draw(Area::new(0., 0., 200., 100.), &mut state);
+        // The text grows to its max-content width
+        assert_eq!(state.draws, vec![Area::new(0., 47.5, 100., 5.)]);
+
+        state.draws.clear();
+        Layout::new(|_: &mut State| row(vec![text(), draw(|_, _| {}).width(195.)]))
+            .draw(Area::new(0., 0., 200., 100.), &mut state);
+        // The text can't shrink below its min-content width
+        assert_eq!(state.draws, vec![Area::new(-2.5, 25., 10., 50.)]);
+    }
+
+    #[test]
+    fn test_measure_explicit() {
+        let mut state = State::default();
+        Layout::new(|_: &mut State| column(vec![text().width(30.).align(Align::Leading), space()]))
+            .draw(Area::new(0., 0., 100., 100.), &mut state);
+        assert_eq!(state.draws, vec![Area::new(0., 0., 30., 20.)]);
+    }
+}
@@ -1,5 +1,26 @@
+pub mod animate_tests;
 pub mod attach_tests;
+pub mod cell_tests;
+pub mod clip_tests;
+pub mod compute_tests;
+pub mod conflict_tests;
+pub mod debug_tests;
+pub mod direction_tests;
 pub mod dynamic_tests;
+pub mod error_tests;
+pub mod export_tests;
+pub mod grid_tests;
 pub mod layout_tests;
+pub mod measure_tests;
+pub mod no_std_tests;
+pub mod pixel_tests;
+pub mod retained_tests;
+pub mod scalar_tests;
 pub mod scope_tests;
+pub mod scroll_tests;
 pub mod sequence_tests;
+pub mod sync_tests;
+pub mod text_tests;
+pub mod transition_tests;
+pub mod virtual_tests;
+pub mod wrap_tests;
@@ -0,0 +1,71 @@
+#[cfg(all(test, not(feature = "std")))]
+mod tests {
+    use crate::math::Float;
+    use crate::traits::Scalar;
+
+    const VALUES: [f32; 18] = [
+        0.,
+        -0.,
+        0.5,
+        -0.5,
+        1.5,
+        -1.5,
+        2.5,
+        0.49999997,
+        -0.49999997,
+        1.,
+        -1.,
+        3.7,
+        -3.7,
+        123.456,
+        -98765.4,
+        1e9,
+        -1e9,
+        8388609.,
+    ];
+
+    fn close(a: f32, b: f32) -> bool {
+        a == b || (a - b).abs() <= 1e-6 * b.abs().max(1.) || (a.is_nan() && b.is_nan())
+    }
+
+    #[test]
+    fn test_rounding_matches_std() {
+        for value in VALUES {
+            assert_eq!(Scalar::floor(value), f32::floor(value), "floor {value}");
+            assert_eq!(Scalar::round(value), f32::round(value), "round {value}");
+            assert_eq!(Scalar::floor(value as f64), f64::floor(value as f64));
+            assert_eq!(Scalar::round(value as f64), f64::round(value as f64));
+        }
+        for value in [f32::INFINITY, f32::NEG_INFINITY] {
+            assert_eq!(Scalar::floor(value), value);
+            assert_eq!(Scalar::round(value), value);
+        }
+        assert!(Scalar::floor(f32::NAN).is_nan());
+        assert!(Scalar::round(-0.4_f32).is_sign_negative());
+    }
+
+    #[test]
+    fn test_functions_match_std() {
+        for value in VALUES.into_iter().filter(|value| value.abs() < 1e6) {
+            assert!(close(Float::sin(value), f32::sin(value)), "sin {value}");
+            assert!(close(Float::cos(value), f32::cos(value)), "cos {value}");
+            assert!(
+                close(Float::powi(value, 3), f32::powi(value, 3)),
+                "powi {value}"
+            );
+            assert!(close(Float::powi(value, -2), f32::powi(value, -2)));
+            let exponent = value / 1000.;
+            assert!(
+                close(Float::exp(exponent), f32::exp(exponent)),
+                "exp {value}"
+            );
+            assert!(
+                close(Float::sqrt(value.abs()), f32::sqrt(value.abs())),
+                "sqrt {value}"
+            );
+        }
+        assert!(close(Float::exp(-20_f32), f32::exp(-20.)));
+        assert_eq!(Float::exp(200_f32), f32::INFINITY);
+        assert!(Float::sqrt(-1_f32).is_nan());
+    }
+}
@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+
+    #[test]
+    fn test_siblings_share_edges() {
+        let mut drawn = Vec::new();
+        Layout::new(|_: &mut Vec<Area>| {
+            row((0..7)
+                .map(|_| draw(|area, drawn: &mut Vec<Area>| drawn.push(area)))
+                .collect())
+        })
+        .pixel_snapping(1.5)
+        .draw(Area::new(0.3, 0., 100., 10.), &mut drawn);
+        assert_eq!(drawn.len(), 7);
+        drawn.sort_by(|a, b| a.x.total_cmp(&b.x));
+        assert_eq!(drawn[0].x, 0.);
+        for pair in drawn.windows(2) {
+            assert_eq!(pair[0].x + pair[0].width, pair[1].x);
+        }
+        assert_eq!(drawn[6].x + drawn[6].width, 150.);
+        for area in drawn {
+            assert_eq!(area.x.fract(), 0.);
+            assert_eq!(area.width.fract(), 0.);
+            assert!(area.width == 21. || area.width == 22.);
+            assert_eq!(area.height, 15.);
+        }
+    }
+
+    #[test]
+    fn test_snapped_areas_are_consistent() {
+        let mut layout = Layout::new(|_: &mut ()| {
+            column_spaced(
+                0.7,
+                vec![
+                    draw(|area, _| assert_eq!(area, Area::new(3., 3., 61., 38.))).pad(1.3),
+                    draw(|area, _| assert_eq!(area, Area::new(0., 45., 67., 44.))).id("bottom"),
+                ],
+            )
+            .id("column")
+        })
+        .pixel_snapping(2.);
+        let mut computed = layout.compute(Area::new(0., 0., 33.3, 44.4), &mut ());
+        assert_eq!(computed.area("column"), Some(Area::new(0., 0., 67., 89.)));
+        assert_eq!(computed.area("bottom"), Some(Area::new(0., 45., 67., 44.)));
+        assert_eq!(computed.debug_nodes()[0].area, Area::new(0., 0., 67., 89.));
+        computed.draw(&mut ());
+
+        layout.set_pixel_snapping(None);
+        let computed = layout.compute(Area::new(0., 0., 33.3, 44.4), &mut ());
+        assert_eq!(computed.area("column"), Some(Area::new(0., 0., 33.3, 44.4)));
+    }
+}
@@ -0,0 +1,114 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::node_cache::NodeCache;
+    use crate::nodes::*;
+    use crate::Node;
+
+    fn clean<State, Ctx>(node: &NodeCache<State, Ctx, f32>) -> Vec<bool> {
+        std::iter::once(node.clean)
+            .chain(node.kind.children().into_iter().flat_map(clean))
+            .collect()
+    }
+
+    struct State {
+        width: f32,
+        swap: bool,
+        draws: Vec<Area>,
+    }
+
+    fn layout(state: &mut State) -> Node<State> {
+        row(vec![
+            draw(|a, s: &mut State| s.draws.push(a)).width(state.width),
+            column(if state.swap {
+                vec![
+                    draw(|a, s: &mut State| s.draws.push(a)).id(2),
+                    draw(|a, s: &mut State| s.draws.push(a)).id(1),
+                ]
+            } else {
+                vec![
+                    draw(|a, s: &mut State| s.draws.push(a)).id(1),
+                    draw(|a, s: &mut State| s.draws.push(a)).id(2),
+                ]
+            }),
+        ])
+    }
+
+    #[test]
+    fn test_retained_unchanged() {
+        let mut state = State {
+            width: 20.,
+            swap: false,
+            draws: vec![],
+        };
+        let layout = Layout::new(layout).retained();
+        let area = Area::new(0., 0., 100., 100.);
+        let first = layout.compute(area, &mut state);
+        assert!(clean(&first.layout).iter().all(|clean| !clean));
+
+        let mut second = layout.compute(area, &mut state);
+        assert!(clean(&second.layout).iter().all(|clean| *clean));
+        assert_eq!(first.root(), second.root());
+        second.draw(&mut state);
+        assert_eq!(
+            state.draws,
+            vec![
+                Area::new(20., 50., 80., 50.),
+                Area::new(20., 0., 80., 50.),
+                Area::new(0., 0., 20., 100.),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_retained_changes() {
+        let mut state = State {
+            width: 20.,
+            swap: false,
+            draws: vec![],
+        };
+        let layout = Layout::new(layout).retained();
+        let area = Area::new(0., 0., 100., 100.);
+        layout.compute(area, &mut state);
+
+        state.width = 40.;
+        let computed = layout.compute(area, &mut state);
+        assert_eq!(
+            clean(&computed.layout),
+            vec![false, false, true, true, true, true, true, true]
+        );
+        assert_eq!(
+            computed.root().children[0].area,
+            Area::new(0., 0., 40., 100.)
+        );
+        assert_eq!(computed.area(1), Some(Area::new(40., 0., 60., 50.)));
+
+        state.swap = true;
+        let computed = layout.compute(area, &mut state);
+        assert_eq!(
+            clean(&computed.layout),
+            vec![false, true, true, false, true, true, true, true]
+        );
+        assert_eq!(computed.area(1), Some(Area::new(40., 50., 60., 50.)));
+        assert_eq!(computed.area(2), Some(Area::new(40., 0., 60., 50.)));
+
+        let computed = layout.compute(Area::new(0., 0., 100., 50.), &mut state);
+        assert_eq!(computed.area(1), Some(Area::new(40., 25., 60., 25.)));
+    }
+
+    #[test]
+    fn test_retained_closures() {
+        let layout = Layout::new(|()| {
+            column(vec![
+                draw(|_, _| {}).dynamic_height(|w, _| w * 0.5),
+                draw(|_, _| {}),
+            ])
+        })
+        .retained();
+        let area = Area::new(0., 0., 100., 100.);
+        layout.compute(area, &mut ());
+        let computed = layout.compute(area, &mut ());
+        assert_eq!(clean(&computed.layout), vec![false, false, true, true]);
+    }
+}
@@ -0,0 +1,213 @@
+#[cfg(test)]
+mod tests {
+    use crate::constraints::{Constraint, SizeConstraints};
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::traits::Scalar;
+    use crate::{Layout, NodeWith};
+    use std::ops::{Add, Div, Mul, Sub};
+
+    /// A fixed-point number with 16 fractional bits
+    #[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+    struct Fixed(i64);
+
+    impl Add for Fixed {
+        type Output = Self;
+        fn add(self, other: Self) -> Self {
+            Fixed(self.0 + other.0)
+        }
+    }
+    impl Sub for Fixed {
+        type Output = Self;
+        fn sub(self, other: Self) -> Self {
+            Fixed(self.0 - other.0)
+        }
+    }
+    impl Mul for Fixed {
+        type Output = Self;
+        fn mul(self, other: Self) -> Self {
+            Fixed((self.0 * other.0) >> 16)
+        }
+    }
+    impl Div for Fixed {
+        type Output = Self;
+        fn div(self, other: Self) -> Self {
+            Fixed((self.0 << 16) / other.0)
+        }
+    }
+    impl Scalar for Fixed {
+        const ZERO: Self = Fixed(0);
+        const ONE: Self = Fixed(1 << 16);
+        fn from_f64(value: f64) -> Self {
+            Fixed((value * 65536.) as i64)
+        }
+        fn to_f64(self) -> f64 {
+            self.0 as f64 / 65536.
+        }
+        fn to_bits(self) -> u64 {
+            self.0 as u64
+        }
+        fn floor(self) -> Self {
+            Fixed(self.0 & !0xffff)
+        }
+        fn round(self) -> Self {
+            Fixed(self.0 + 0x8000).floor()
+        }
+    }
+
+    fn fixed(sizes: &[Option<f64>]) -> Vec<SizeConstraints<Fixed>> {
+        sizes
+            .iter()
+            .map(|size| {
+                let size = size.map(Fixed::from_f64);
+                SizeConstraints {
+                    width: Constraint::new(size, size),
+                    ..Default::default()
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_f64_keeps_precision_far_from_origin() {
+        let available = Area {
+            x: 1e9,
+            y: 0.,
+            width: 101.,
+            height: 10.,
+        };
+        let areas = axis_areas::<f64>(
+            &[SizeConstraints::default(), SizeConstraints::default()],
+            0.,
+            available,
+            Orientation::Horizontal,
+            XAlign::Center,
+            YAlign::Center,
+            None,
+            false,
+        );
+        assert_eq!(areas[0].width, 50.5);
+        assert_eq!(areas[1].x, 1e9 + 50.5);
+        assert_eq!(
+            areas[1].intersection(available),
+            Area {
+                x: 1e9 + 50.5,
+                y: 0.,
+                width: 50.5,
+                height: 10.,
+            }
+        );
+    }
+
+    #[test]
+    fn test_f64_layout_keeps_precision_far_from_origin() {
+        type Node64 = NodeWith<Vec<Area<f64>>, (), f64>;
+        let layout = Layout::new(|_: &mut Vec<Area<f64>>| {
+            Node64::row_spaced(
+                1.,
+                vec![
+                    Node64::space().id("inner").pad(0.25),
+                    Node64::column(vec![Node64::draw(|area, drawn, _| drawn.push(area))
+                        .id("cell")
+                        .height(10.5)])
+                    .width(20.125),
+                ],
+            )
+        })
+        .retained();
+        let area = Area {
+            x: 1e9,
+            y: 1e9,
+            width: 101.,
+            height: 100.,
+        };
+        let cell = Area {
+            x: 1e9 + 80.875,
+            y: 1e9 + 44.75,
+            width: 20.125,
+            height: 10.5,
+        };
+        // The second frame reuses the retained results of the first
+        for _ in 0..2 {
+            let mut drawn = Vec::new();
+            let mut computed = layout.compute(area, &mut drawn);
+            assert_eq!(
+                computed.area("inner"),
+                Some(Area {
+                    x: 1e9 + 0.25,
+                    y: 1e9 + 0.25,
+                    width: 79.375,
+                    height: 99.5,
+                })
+            );
+            assert_eq!(computed.area("cell"), Some(cell));
+            let hits = computed.hit_test(1e9 + 80.9, 1e9 + 50.);
+            assert_eq!(hits.last().map(|node| node.area), Some(cell));
+            computed.draw(&mut drawn);
+            assert_eq!(drawn, vec![cell]);
+        }
+    }
+
+    #[test]
+    fn test_f64_matches_f32() {
+        let bounds = [(Some(10.), Some(20.)), (None, Some(5.)), (Some(30.), None)];
+        let weights = [1., 2., 1.];
+        let (sizes, pool) = distribute(100_f32, &bounds, &weights);
+        let (wide_sizes, wide_pool) = distribute(
+            100_f64,
+            &bounds.map(|(lower, upper)| (lower.map(f64::from), upper.map(f64::from))),
+            &weights.map(f64::from),
+        );
+        assert_eq!(pool as f64, wide_pool);
+        for (size, wide) in sizes.iter().zip(wide_sizes) {
+            assert!((*size as f64 - wide).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_fixed_point_row() {
+        let areas = axis_areas(
+            &fixed(&[Some(10.), None, None]),
+            Fixed::from_f64(2.),
+            Area {
+                x: Fixed::ZERO,
+                y: Fixed::ZERO,
+                width: Fixed::from_f64(100.),
+                height: Fixed::from_f64(10.),
+            },
+            Orientation::Horizontal,
+            XAlign::Center,
+            YAlign::Center,
+            None,
+            false,
+        );
+        let areas: Vec<(f64, f64)> = areas
+            .iter()
+            .map(|area| (area.x.to_f64(), area.width.to_f64()))
+            .collect();
+        assert_eq!(areas, vec![(0., 10.), (12., 43.), (57., 43.)]);
+    }
+
+    #[test]
+    fn test_fixed_point_cells() {
+        let areas = axis_areas(
+            &fixed(&[None, None, None]),
+            Fixed::ZERO,
+            Area {
+                x: Fixed::ZERO,
+                y: Fixed::ZERO,
+                width: Fixed::from_f64(10.),
+                height: Fixed::ONE,
+            },
+            Orientation::Horizontal,
+            XAlign::Center,
+            YAlign::Center,
+            None,
+            true,
+        );
+        let widths: Vec<f64> = areas.iter().map(|area| area.width.to_f64()).collect();
+        assert_eq!(widths, vec![4., 3., 3.]);
+        // The rounding error of 10 / 3 in 16 fractional bits is well under a cell
+        assert!(((areas[2].x + areas[2].width).to_f64() - 10.).abs() < 0.001);
+    }
+}
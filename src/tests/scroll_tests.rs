@@ -0,0 +1,167 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+    use crate::Node;
+
+    #[derive(Default)]
+    struct State {
+        offset: (f32, f32),
+        info: Option<ScrollInfo>,
+        offsets: usize,
+        draws: Vec<(usize, Area, Option<Area>)>,
+    }
+
+    fn item(i: usize) -> Node<State> {
+        draw_clipped(move |area, clip, state: &mut State| state.draws.push((i, area, clip)))
+    }
+
+    fn list(axis: ScrollAxis) -> impl Fn(&mut State) -> Node<State> {
+        move |_| {
+            scroll(
+                axis,
+                |info, state: &mut State| {
+                    state.info = Some(info);
+                    state.offsets += 1;
+                    state.offset
+                },
+                column((0..10).map(|i| item(i).height(40.)).collect()),
+            )
+            .pad(10.)
+        }
+    }
+
+    #[test]
+    fn test_scroll_vertical() {
+        let mut state = State {
+            offset: (30., 50.),
+            ..Default::default()
+        };
+        Layout::new(list(ScrollAxis::Vertical)).draw(Area::new(0., 0., 120., 120.), &mut state);
+        let viewport = Area::new(10., 10., 100., 100.);
+        assert_eq!(
+            state.info,
+            Some(ScrollInfo {
+                viewport,
+                content_width: 100.,
+                content_height: 400.,
+            })
+        );
+        // Only items overlapping the viewport are drawn, & the horizontal offset is ignored
+        let mut draws = state.draws.clone();
+        draws.sort_by_key(|(i, _, _)| *i);
+        assert_eq!(
+            draws,
+            vec![
+                (1, Area::new(10., 0., 100., 40.), Some(viewport)),
+                (2, Area::new(10., 40., 100., 40.), Some(viewport)),
+                (3, Area::new(10., 80., 100., 40.), Some(viewport)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scroll_clamped() {
+        let layout = Layout::new(list(ScrollAxis::Vertical));
+        let area = Area::new(0., 0., 120., 120.);
+        let mut state = State {
+            offset: (0., 1000.),
+            ..Default::default()
+        };
+        let computed = layout.compute(area, &mut state);
+        let content = &computed.root().children[0].children[0];
+        assert_eq!(content.area, Area::new(10., -290., 100., 400.));
+
+        state.offset = (0., -50.);
+        let computed = layout.compute(area, &mut state);
+        let content = &computed.root().children[0].children[0];
+        assert_eq!(content.area, Area::new(10., 10., 100., 400.));
+    }
+
+    #[test]
+    fn test_scroll_offset_once_per_frame() {
+        let mut state = State::default();
+        let layout = Layout::new(|state: &mut State| {
+            // The row measures the scroll's constraints before laying it out
+            row(vec![list(ScrollAxis::Vertical)(state), space().width(20.)])
+        });
+        let area = Area::new(0., 0., 140., 120.);
+        layout.compute(area, &mut state);
+        assert_eq!(state.offsets, 1);
+        layout.draw(area, &mut state);
+        assert_eq!(state.offsets, 2);
+    }
+
+    #[test]
+    fn test_scroll_fills_viewport() {
+        let mut state = State::default();
+        let computed = Layout::new(|_: &mut State| {
+            scroll(
+                ScrollAxis::Both,
+                |_, _| (20., 20.),
+                draw(|_, _| {}).width(50.).height(500.),
+            )
+        })
+        .compute(Area::new(0., 0., 100., 100.), &mut state);
+        let scroll = computed.root();
+        assert_eq!(scroll.kind, NodeKind::Scroll);
+        assert_eq!(scroll.area, Area::new(0., 0., 100., 100.));
+        // The content is narrower than the viewport, so it can't scroll horizontally
+        assert_eq!(scroll.children[0].area, Area::new(25., -20., 50., 500.));
+    }
+
+    #[test]
+    fn test_scroll_nested_clip() {
+        let mut state = State {
+            offset: (40., 0.),
+            ..Default::default()
+        };
+        Layout::new(|_: &mut State| {
+            scroll(
+                ScrollAxis::Vertical,
+                |_, _| (0., 0.),
+                column(vec![
+                    scroll(
+                        ScrollAxis::Horizontal,
+                        |_, state: &mut State| state.offset,
+                        row(vec![item(0).width(100.), item(1).width(100.)]),
+                    )
+                    .height(50.),
+                    space().height(200.),
+                ]),
+            )
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut state);
+        let clip = Some(Area::new(0., 0., 100., 50.));
+        assert_eq!(
+            state.draws,
+            vec![
+                (1, Area::new(60., 0., 100., 50.), clip),
+                (0, Area::new(-40., 0., 100., 50.), clip),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scroll_hit_test() {
+        let computed = Layout::new(|_: &mut ()| {
+            scroll(
+                ScrollAxis::Vertical,
+                |_, _| (0., 60.),
+                column(vec![
+                    draw(|_, _| {}).height(50.).id("hidden"),
+                    draw(|_, _| {}).height(200.).id("visible"),
+                ]),
+            )
+            .height(50.)
+            .id("scroll")
+        })
+        .compute(Area::new(0., 0., 100., 100.), &mut ());
+        let hits = computed.hit_test(50., 30.);
+        assert_eq!(hits.len(), 2);
+        assert!(hits[0].has_id("visible"));
+        assert!(hits[1].has_id("scroll"));
+        assert!(computed.hit_test(50., 20.).is_empty());
+    }
+}
@@ -394,7 +394,7 @@ mod tests {
         assert_eq!(
             row::<(), ()>(vec![space(), space().height(30.)])
                 .inner
-                .constraints(Area::zero(), &mut (), &mut ()),
+                .constraints(Area::zero(), false, &mut (), &mut ()),
             SizeConstraints {
                 width: Constraint::none(),
                 height: Constraint::new(Some(30.), None),
@@ -404,7 +404,7 @@ mod tests {
         assert_eq!(
             row::<(), ()>(vec![space().height(40.), space().height(30.)])
                 .inner
-                .constraints(Area::zero(), &mut (), &mut ()),
+                .constraints(Area::zero(), false, &mut (), &mut ()),
             SizeConstraints {
                 width: Constraint::none(),
                 height: Constraint::new(Some(40.), Some(40.)),
@@ -414,7 +414,7 @@ mod tests {
         assert_eq!(
             column::<(), ()>(vec![space(), space().width(10.)])
                 .inner
-                .constraints(Area::zero(), &mut (), &mut ()),
+                .constraints(Area::zero(), false, &mut (), &mut ()),
             SizeConstraints {
                 width: Constraint::new(Some(10.), None),
                 height: Constraint::none(),
@@ -424,7 +424,7 @@ mod tests {
         assert_eq!(
             column::<(), ()>(vec![space().width(20.), space().width(10.)])
                 .inner
-                .constraints(Area::zero(), &mut (), &mut ()),
+                .constraints(Area::zero(), false, &mut (), &mut ()),
             SizeConstraints {
                 width: Constraint::new(Some(20.), Some(20.)),
                 height: Constraint::none(),
@@ -434,7 +434,7 @@ mod tests {
         assert_eq!(
             stack::<(), ()>(vec![space(), space().height(10.)])
                 .inner
-                .constraints(Area::zero(), &mut (), &mut ()),
+                .constraints(Area::zero(), false, &mut (), &mut ()),
             SizeConstraints {
                 width: Constraint::none(),
                 height: Constraint::new(Some(10.), None),
@@ -444,7 +444,7 @@ mod tests {
         assert_eq!(
             stack::<(), ()>(vec![space().height(20.), space().width(10.)])
                 .inner
-                .constraints(Area::zero(), &mut (), &mut ()),
+                .constraints(Area::zero(), false, &mut (), &mut ()),
             SizeConstraints {
                 width: Constraint::new(Some(10.), None),
                 height: Constraint::new(Some(20.), None),
@@ -454,7 +454,7 @@ mod tests {
         assert_eq!(
             stack::<(), ()>(vec![space().height(20.), space().height(10.)])
                 .inner
-                .constraints(Area::zero(), &mut (), &mut ()),
+                .constraints(Area::zero(), false, &mut (), &mut ()),
             SizeConstraints {
                 width: Constraint::none(),
                 height: Constraint::new(Some(20.), Some(20.)),
@@ -464,7 +464,7 @@ mod tests {
         assert_eq!(
             stack::<(), ()>(vec![space().width(20.), space().width(10.)])
                 .inner
-                .constraints(Area::zero(), &mut (), &mut ()),
+                .constraints(Area::zero(), false, &mut (), &mut ()),
             SizeConstraints {
                 width: Constraint::new(Some(20.), Some(20.)),
                 height: Constraint::none(),
@@ -480,7 +480,7 @@ mod tests {
                 .pad(0.)
                 .width_range(..5.)
                 .inner
-                .constraints(Area::zero(), &mut (), &mut ()),
+                .constraints(Area::zero(), false, &mut (), &mut ()),
             SizeConstraints {
                 width: Constraint::new(Some(5.), Some(5.)),
                 height: Constraint::none(),
@@ -488,4 +488,134 @@ mod tests {
             }
         );
     }
+    #[test]
+    fn test_seq_weights() {
+        Layout::new(|()| {
+            row(vec![
+                draw(|a, _| {
+                    assert_eq!(a, Area::new(0., 0., 60., 100.));
+                })
+                .weight(2.),
+                draw(|a, _| {
+                    assert_eq!(a, Area::new(60., 0., 30., 100.));
+                }),
+                draw(|a, _| {
+                    assert_eq!(a, Area::new(90., 0., 10., 100.));
+                })
+                .width(10.),
+            ])
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+        Layout::new(|()| {
+            column(vec![
+                draw(|a, _| {
+                    assert_eq!(a, Area::new(0., 0., 100., 30.));
+                })
+                .weight(3.)
+                .height_range(..30.),
+                draw(|a, _| {
+                    assert_eq!(a, Area::new(0., 30., 100., 70.));
+                }),
+            ])
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+        Layout::new(|()| {
+            row_spaced(
+                10.,
+                vec![
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(0., 0., 20., 100.));
+                    })
+                    .weight(1.)
+                    .width_range(20.0..),
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(30., 0., 70., 100.));
+                    })
+                    .weight(9.),
+                ],
+            )
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_seq_weights_padded() {
+        Layout::new(|()| {
+            row(vec![
+                draw(|a, _| {
+                    assert_eq!(a, Area::new(5., 5., 65., 90.));
+                })
+                .weight(3.)
+                .pad(5.),
+                draw(|a, _| {
+                    assert_eq!(a, Area::new(75., 0., 25., 100.));
+                }),
+            ])
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+        Layout::new(|()| {
+            row(vec![
+                draw(|a, _| {
+                    assert_eq!(a, Area::new(5., 30., 65., 40.));
+                })
+                .weight(3.)
+                .pad(5.)
+                .height(50.),
+                draw(|a, _| {
+                    assert_eq!(a, Area::new(75., 0., 25., 100.));
+                }),
+            ])
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_seq_distribution() {
+        for (distribution, positions) in [
+            (Distribution::SpaceBetween, [0., 30., 60., 90.]),
+            (Distribution::SpaceAround, [7.5, 32.5, 57.5, 82.5]),
+            (Distribution::SpaceEvenly, [12., 34., 56., 78.]),
+        ] {
+            Layout::new(move |()| {
+                row(positions
+                    .into_iter()
+                    .map(|x| {
+                        draw(move |a, _| {
+                            assert_eq!(a, Area::new(x, 0., 10., 100.));
+                        })
+                        .width(10.)
+                    })
+                    .collect())
+                .distribute(distribution)
+                .expand()
+            })
+            .draw(Area::new(0., 0., 100., 100.), &mut ());
+        }
+        Layout::new(|()| {
+            column_spaced(
+                10.,
+                vec![
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(0., 20., 100., 15.));
+                    })
+                    .height(15.),
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(0., 65., 100., 15.));
+                    })
+                    .height(15.),
+                ],
+            )
+            .distribute(Distribution::SpaceEvenly)
+            .expand()
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+        Layout::new(|()| {
+            row(vec![draw(|a, _| {
+                assert_eq!(a, Area::new(90., 0., 10., 100.));
+            })
+            .width(10.)])
+            .align_contents(Align::Trailing)
+            .distribute(Distribution::SpaceBetween)
+            .expand()
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
 }
@@ -0,0 +1,57 @@
+#[cfg(all(test, feature = "sync"))]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+    use crate::NodeWith;
+    use std::{sync::Arc, thread};
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_layouts_are_send_sync() {
+        assert_send_sync::<Layout<(), ()>>();
+        assert_send_sync::<ComputedLayout<(), ()>>();
+        assert_send_sync::<NodeWith<(), ()>>();
+        // Layouts are thread-safe whether or not the state is
+        assert_send_sync::<Layout<std::rc::Rc<()>, ()>>();
+    }
+
+    #[test]
+    fn test_compute_on_worker_thread() {
+        let label = Arc::new("label".to_string());
+        let layout = Layout::new(move |_: &mut Vec<(String, Area)>| {
+            let label = label.clone();
+            row(vec![
+                draw(move |area, drawn: &mut Vec<(String, Area)>| {
+                    drawn.push((label.to_string(), area))
+                })
+                .width(10.),
+                scope::<_, _, crate::traits::NoOpScoper<_>>(|_| space()),
+            ])
+        });
+        let mut computed =
+            thread::spawn(move || layout.compute(Area::new(0., 0., 100., 100.), &mut Vec::new()))
+                .join()
+                .unwrap();
+        let mut drawn = Vec::new();
+        computed.draw(&mut drawn);
+        assert_eq!(
+            drawn,
+            vec![("label".to_string(), Area::new(0., 0., 10., 100.))]
+        );
+    }
+
+    #[test]
+    fn test_share_layout_between_threads() {
+        let layout = Arc::new(Layout::new(|_: &mut ()| space().id("space")).retained());
+        let area = Area::new(0., 0., 10., 10.);
+        let worker = {
+            let layout = layout.clone();
+            thread::spawn(move || layout.compute(area, &mut ()).area("space"))
+        };
+        let computed = layout.compute(area, &mut ());
+        assert_eq!(worker.join().unwrap(), Some(area));
+        assert_eq!(computed.area("space"), Some(area));
+    }
+}
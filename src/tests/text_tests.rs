@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+    use crate::traits::TextMeasurer;
+
+    #[test]
+    fn test_monospace_wrap() {
+        let measurer = Monospace::default();
+        assert_eq!(
+            measurer.wrap("the quick brown fox", Some(10.)),
+            vec!["the quick", "brown fox"]
+        );
+        assert_eq!(
+            measurer.wrap("the quick brown fox", Some(4.)),
+            vec!["the", "quick", "brown", "fox"]
+        );
+        assert_eq!(
+            measurer.wrap("the quick\n\nbrown fox", None),
+            vec!["the quick", "", "brown fox"]
+        );
+        assert_eq!(
+            Monospace::new(2., 3.).measure_text("the quick brown fox", &(), Some(20.)),
+            (18., 6.)
+        );
+    }
+
+    #[test]
+    fn test_text_height() {
+        let mut draws = Vec::new();
+        Layout::new_with(|_: &mut Vec<Area>, _: &mut Monospace| {
+            column(vec![
+                text(
+                    "the quick brown fox",
+                    (),
+                    |area, draws: &mut Vec<Area>, _| draws.push(area),
+                ),
+                text(
+                    "jumps over\nthe lazy dog",
+                    (),
+                    |area, draws: &mut Vec<Area>, _| draws.push(area),
+                ),
+                space(),
+            ])
+            .width(10.)
+        })
+        .draw_with(
+            Area::new(0., 0., 20., 20.),
+            &mut draws,
+            &mut Monospace::default(),
+        );
+        assert_eq!(
+            draws,
+            vec![Area::new(5., 2., 10., 3.), Area::new(5., 0., 10., 2.)]
+        );
+    }
+
+    #[test]
+    fn test_text_min_content() {
+        let mut draws = Vec::new();
+        Layout::new_with(|_: &mut Vec<Area>, _: &mut Monospace| {
+            row(vec![
+                text("a longer sentence", (), |area, draws: &mut Vec<Area>, _| {
+                    draws.push(area)
+                }),
+                draw_with(|_, _, _| {}).width_range(15.0..),
+            ])
+        })
+        .draw_with(
+            Area::new(0., 0., 20., 20.),
+            &mut draws,
+            &mut Monospace::default(),
+        );
+        // The text can't be narrower than its longest word
+        assert_eq!(draws, vec![Area::new(-1.5, 9., 8., 2.)]);
+    }
+}
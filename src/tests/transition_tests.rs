@@ -0,0 +1,196 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+    use crate::Node;
+    use std::time::Duration;
+
+    #[derive(Default)]
+    struct State {
+        shown: bool,
+        duplicated: bool,
+        drawn: Vec<(&'static str, Area, f32)>,
+    }
+
+    fn layout(state: &mut State) -> Node<State> {
+        column(vec![
+            draw(|area, state: &mut State| state.drawn.push(("first", area, 1.))).height(10.),
+            if state.shown {
+                draw_faded(|area, opacity, state: &mut State| {
+                    state.drawn.push(("shown", area, opacity))
+                })
+                .height(20.)
+                .id("shown")
+                .transition(
+                    Transition::new(Duration::from_secs(1), Easing::Linear)
+                        .fade()
+                        .slide(10., 0.),
+                )
+            } else {
+                empty()
+            },
+            draw(|area, state: &mut State| state.drawn.push(("last", area, 1.))).height(10.),
+        ])
+    }
+
+    fn frame(
+        layout: &mut Layout<State, ()>,
+        state: &mut State,
+        millis: u64,
+    ) -> Vec<(&'static str, Area, f32)> {
+        layout.set_time(Duration::from_millis(millis));
+        state.drawn.clear();
+        layout.draw(Area::new(0., 0., 100., 100.), state);
+        let mut drawn = state.drawn.clone();
+        drawn.sort_by(|a, b| a.1.y.total_cmp(&b.1.y));
+        drawn
+    }
+
+    #[test]
+    fn test_transition_enter() {
+        let mut layout = Layout::new(layout);
+        let mut state = State::default();
+        frame(&mut layout, &mut state, 0);
+        state.shown = true;
+        assert_eq!(
+            frame(&mut layout, &mut state, 1000)[1],
+            ("shown", Area::new(10., 40., 100., 20.), 0.)
+        );
+        assert!(layout.is_animating());
+        assert_eq!(
+            frame(&mut layout, &mut state, 1500)[1],
+            ("shown", Area::new(5., 40., 100., 20.), 0.5)
+        );
+        assert_eq!(
+            frame(&mut layout, &mut state, 2000)[1],
+            ("shown", Area::new(0., 40., 100., 20.), 1.)
+        );
+        assert!(!layout.is_animating());
+    }
+
+    #[test]
+    fn test_transition_not_on_first_frame() {
+        let mut layout = Layout::new(layout);
+        let mut state = State {
+            shown: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            frame(&mut layout, &mut state, 0)[1],
+            ("shown", Area::new(0., 40., 100., 20.), 1.)
+        );
+        assert!(!layout.is_animating());
+    }
+
+    #[test]
+    fn test_transition_exit() {
+        let mut layout = Layout::new(layout);
+        let mut state = State {
+            shown: true,
+            ..Default::default()
+        };
+        let shown = frame(&mut layout, &mut state, 0);
+        state.shown = false;
+        // The leaving node keeps its place, so its siblings don't move until it's gone
+        let mut leaving = shown.clone();
+        leaving[1].2 = 0.5;
+        leaving[1].1.x = 5.;
+        assert_eq!(frame(&mut layout, &mut state, 1000), shown);
+        assert!(layout.is_animating());
+        assert_eq!(frame(&mut layout, &mut state, 1500), leaving);
+        assert_eq!(
+            frame(&mut layout, &mut state, 2000),
+            vec![
+                ("first", Area::new(0., 40., 100., 10.), 1.),
+                ("last", Area::new(0., 50., 100., 10.), 1.),
+            ]
+        );
+        assert!(!layout.is_animating());
+    }
+
+    #[test]
+    fn test_rejected_frame_keeps_transitions() {
+        let mut layout = Layout::new(|state: &mut State| {
+            if state.duplicated {
+                stack(vec![layout(state), space().id(0), space().id(0)])
+            } else {
+                layout(state)
+            }
+        });
+        let mut state = State {
+            shown: true,
+            ..Default::default()
+        };
+        let shown = frame(&mut layout, &mut state, 0);
+        state.shown = false;
+        state.duplicated = true;
+        layout.set_time(Duration::from_millis(1000));
+        assert!(layout
+            .try_draw(Area::new(0., 0., 100., 100.), &mut state)
+            .is_err());
+        state.duplicated = false;
+        // The node only starts leaving in the next valid frame
+        let mut leaving = shown.clone();
+        leaving[1].2 = 0.5;
+        leaving[1].1.x = 5.;
+        assert_eq!(frame(&mut layout, &mut state, 1500), shown);
+        assert_eq!(frame(&mut layout, &mut state, 2000), leaving);
+    }
+
+    #[test]
+    fn test_transition_readded_while_leaving() {
+        let mut layout = Layout::new(layout).retained();
+        let mut state = State {
+            shown: true,
+            ..Default::default()
+        };
+        frame(&mut layout, &mut state, 0);
+        state.shown = false;
+        frame(&mut layout, &mut state, 0);
+        assert_eq!(frame(&mut layout, &mut state, 500).len(), 3);
+        state.shown = true;
+        let drawn = frame(&mut layout, &mut state, 500);
+        assert_eq!(drawn.len(), 3);
+        assert_eq!(drawn[1], ("shown", Area::new(10., 40., 100., 20.), 0.));
+    }
+
+    #[test]
+    fn test_transition_through_modifiers() {
+        let mut layout = Layout::new(|state: &mut State| {
+            column(vec![if state.shown {
+                draw_faded(|area, opacity, state: &mut State| {
+                    state.drawn.push(("shown", area, opacity))
+                })
+                .id("shown")
+                .pad(10.)
+                .transition(Transition::new(Duration::from_secs(1), Easing::Linear).fade())
+            } else {
+                empty()
+            }])
+        });
+        let mut state = State::default();
+        frame(&mut layout, &mut state, 0);
+        state.shown = true;
+        frame(&mut layout, &mut state, 0);
+        assert_eq!(
+            frame(&mut layout, &mut state, 500),
+            vec![("shown", Area::new(10., 10., 80., 80.), 0.5)]
+        );
+    }
+
+    #[test]
+    fn test_easing_curves() {
+        assert_eq!(Easing::Spring { damping: 0.3 }.ease(0.), 0.);
+        assert_eq!(Easing::Spring { damping: 0.3 }.ease(1.), 1.);
+        // Underdamped springs overshoot, critically damped springs don't
+        let samples = (1..100).map(|i| i as f32 / 100.);
+        assert!(samples
+            .clone()
+            .any(|t| Easing::Spring { damping: 0.3 }.ease(t) > 1.));
+        assert!(samples
+            .clone()
+            .all(|t| Easing::Spring { damping: 1. }.ease(t) < 1.));
+        assert_eq!(Easing::Custom(|t| t * t).ease(0.5), 0.25);
+    }
+}
@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+
+    #[derive(Default)]
+    struct State {
+        offset: f32,
+        info: Option<ScrollInfo>,
+        built: Vec<usize>,
+        draws: Vec<(usize, Area)>,
+    }
+
+    #[test]
+    fn test_virtual_column() {
+        let layout = Layout::new(|_: &mut State| {
+            scroll(
+                ScrollAxis::Vertical,
+                |info, state: &mut State| {
+                    state.info = Some(info);
+                    (0., state.offset)
+                },
+                virtual_column(100_000, 10., |i, state: &mut State| {
+                    state.built.push(i);
+                    draw(move |area, state: &mut State| state.draws.push((i, area)))
+                }),
+            )
+        });
+        let mut state = State {
+            offset: 505.,
+            ..Default::default()
+        };
+        layout.draw(Area::new(0., 0., 100., 30.), &mut state);
+        assert_eq!(state.info.unwrap().content_height, 1_000_000.);
+        assert_eq!(state.built, (48..56).collect::<Vec<_>>());
+        // Items outside of the viewport are built, but clipped when drawing
+        assert_eq!(
+            state.draws,
+            vec![
+                (50, Area::new(0., -5., 100., 10.)),
+                (51, Area::new(0., 5., 100., 10.)),
+                (52, Area::new(0., 15., 100., 10.)),
+                (53, Area::new(0., 25., 100., 10.)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_virtual_row() {
+        let mut state = State::default();
+        let computed = Layout::new(|_: &mut State| {
+            virtual_row(10, 30., |i, state: &mut State| {
+                state.built.push(i);
+                draw(|_, _| {}).id(i)
+            })
+            .overscan(0)
+        })
+        .compute(Area::new(0., 0., 100., 100.), &mut state);
+        // Without a scroll node, the layout's area is visible
+        assert_eq!(state.built, vec![3, 4, 5, 6]);
+        assert_eq!(computed.root().kind, NodeKind::Virtual);
+        assert_eq!(computed.root().area, Area::new(-100., 0., 300., 100.));
+        assert_eq!(computed.area(3_usize), Some(Area::new(-10., 0., 30., 100.)));
+    }
+
+    #[test]
+    fn test_virtual_bounds() {
+        let layout = Layout::new(|_: &mut State| {
+            scroll(
+                ScrollAxis::Vertical,
+                |_, state: &mut State| (0., state.offset),
+                column(vec![
+                    draw(|_, _| {}).height(100.),
+                    virtual_column(5, 20., |i, state: &mut State| {
+                        state.built.push(i);
+                        if i % 2 == 0 {
+                            draw(|_, _| {})
+                        } else {
+                            empty()
+                        }
+                    })
+                    .overscan(1),
+                ]),
+            )
+        });
+        let area = Area::new(0., 0., 100., 50.);
+        let mut state = State::default();
+        let computed = layout.compute(area, &mut state);
+        // Only the overscan is built while the sequence is scrolled out of view
+        assert_eq!(state.built, vec![0]);
+        assert_eq!(computed.root().children[0].children[1].children.len(), 1);
+
+        state.built.clear();
+        state.offset = 1000.;
+        let computed = layout.compute(area, &mut state);
+        assert_eq!(state.built, vec![1, 2, 3, 4]);
+        let items = &computed.root().children[0].children[1].children;
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1].area, Area::new(0., 30., 100., 20.));
+    }
+
+    #[test]
+    fn test_virtual_item_extents() {
+        let layout = Layout::new(|_: &mut State| {
+            scroll(
+                ScrollAxis::Vertical,
+                |_, _| (0., 100.),
+                virtual_column(500, 20., |i, state: &mut State| {
+                    state.built.push(i);
+                    draw(|_, _| {}).id(i)
+                })
+                .item_extents(|i, _| if i % 2 == 0 { 10. } else { 30. })
+                .overscan(1),
+            )
+        });
+        let mut state = State::default();
+        let computed = layout.compute(Area::new(0., 0., 100., 50.), &mut state);
+        assert_eq!(state.built, vec![4, 5, 6, 7, 8]);
+        assert_eq!(computed.area(5_usize), Some(Area::new(0., -10., 100., 30.)));
+        assert_eq!(computed.area(8_usize), Some(Area::new(0., 60., 100., 10.)));
+        assert_eq!(computed.root().children[0].area.height, 10_000.);
+    }
+
+    #[test]
+    fn test_virtual_without_extent() {
+        for extent in [0., -10., f32::NAN] {
+            let mut state = State::default();
+            Layout::new(move |_: &mut State| {
+                virtual_column(100_000, extent, |i, state: &mut State| {
+                    state.built.push(i);
+                    draw(|_, _| {})
+                })
+            })
+            .draw(Area::new(0., 0., 100., 100.), &mut state);
+            assert!(state.built.is_empty());
+        }
+    }
+}
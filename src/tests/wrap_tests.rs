@@ -0,0 +1,153 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+    #[test]
+    fn test_row_wrap() {
+        Layout::new(|()| {
+            row_wrap_spaced(
+                10.,
+                5.,
+                vec![
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(0., 0., 30., 20.));
+                    })
+                    .width(30.)
+                    .height(20.),
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(40., 0., 30., 20.));
+                    })
+                    .width(30.)
+                    .height(20.),
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(0., 25., 30., 20.));
+                    })
+                    .width(30.)
+                    .height(20.),
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(40., 25., 30., 20.));
+                    })
+                    .width(30.)
+                    .height(20.),
+                ],
+            )
+            .align_contents(Align::TopLeading)
+            .expand()
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_row_wrap_hugs_lines() {
+        Layout::new(|()| {
+            row_wrap_spaced(
+                10.,
+                5.,
+                vec![
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(15., 27.5, 30., 20.));
+                    })
+                    .width(30.)
+                    .height(20.),
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(55., 27.5, 30., 20.));
+                    })
+                    .width(30.)
+                    .height(20.),
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(15., 52.5, 30., 20.));
+                    })
+                    .width(30.)
+                    .height(20.),
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(55., 52.5, 30., 20.));
+                    })
+                    .width(30.)
+                    .height(20.),
+                ],
+            )
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_row_wrap_unconstrained() {
+        Layout::new(|()| {
+            row_wrap(vec![
+                draw(|a, _| {
+                    assert_eq!(a, Area::new(0., 0., 30., 10.));
+                })
+                .width(30.)
+                .height(10.),
+                draw(|a, _| {
+                    assert_eq!(a, Area::new(0., 10., 100., 10.));
+                })
+                .height(10.),
+            ])
+            .align_contents(Align::TopLeading)
+            .expand()
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_row_wrap_align_in_line() {
+        Layout::new(|()| {
+            row_wrap(vec![
+                draw(|a, _| {
+                    assert_eq!(a, Area::new(0., 0., 30., 10.));
+                })
+                .width(30.)
+                .height(10.),
+                draw(|a, _| {
+                    assert_eq!(a, Area::new(30., 0., 30., 20.));
+                })
+                .width(30.)
+                .height(20.),
+            ])
+            .align_contents(Align::TopLeading)
+            .expand()
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+        Layout::new(|()| {
+            row_wrap(vec![
+                draw(|a, _| {
+                    assert_eq!(a, Area::new(70., 90., 10., 10.));
+                })
+                .width(10.)
+                .height(10.),
+                draw(|a, _| {
+                    assert_eq!(a, Area::new(80., 80., 20., 20.));
+                })
+                .width(20.)
+                .height(20.),
+            ])
+            .align_contents(Align::BottomTrailing)
+            .expand()
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_column_wrap() {
+        Layout::new(|()| {
+            column_wrap(vec![
+                draw(|a, _| {
+                    assert_eq!(a, Area::new(0., 0., 20., 40.));
+                })
+                .width(20.)
+                .height(40.),
+                draw(|a, _| {
+                    assert_eq!(a, Area::new(0., 40., 20., 40.));
+                })
+                .width(20.)
+                .height(40.),
+                draw(|a, _| {
+                    assert_eq!(a, Area::new(20., 0., 20., 40.));
+                })
+                .width(20.)
+                .height(40.),
+            ])
+            .align_contents(Align::TopLeading)
+            .expand()
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+}
@@ -0,0 +1,15 @@
+/// `Send + Sync` when the `sync` feature is enabled, so layouts can be computed on one thread & drawn on another.
+///
+/// Closures passed to backer must implement `MaybeSync`, which every type does when the feature is disabled.
+#[cfg(feature = "sync")]
+pub trait MaybeSync: Send + Sync {}
+#[cfg(feature = "sync")]
+impl<T: Send + Sync + ?Sized> MaybeSync for T {}
+
+/// `Send + Sync` when the `sync` feature is enabled, so layouts can be computed on one thread & drawn on another.
+///
+/// Closures passed to backer must implement `MaybeSync`, which every type does when the feature is disabled.
+#[cfg(not(feature = "sync"))]
+pub trait MaybeSync {}
+#[cfg(not(feature = "sync"))]
+impl<T: ?Sized> MaybeSync for T {}
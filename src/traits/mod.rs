@@ -1,9 +1,15 @@
+mod maybe_sync;
 mod nodetrait;
+mod scalar;
 mod scopable;
 mod scopable_option;
+mod text_measurer;
 
+pub use maybe_sync::MaybeSync;
 pub(crate) use nodetrait::NodeTrait;
+pub use scalar::Scalar;
 pub use scopable::NoOpScoper;
 pub use scopable::Scopable;
 pub(crate) use scopable::VoidScoper;
 pub use scopable_option::ScopableOption;
+pub use text_measurer::TextMeasurer;
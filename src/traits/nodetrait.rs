@@ -1,23 +1,44 @@
+use crate::prelude::*;
 use crate::{
+    animation::Animator,
     constraints::SizeConstraints,
-    models::{Area, XAlign, YAlign},
+    models::{Area, ComputedNode, ConstraintConflict, DebugNode, Direction, XAlign, YAlign},
+    traits::MaybeSync,
 };
-use std::fmt::Debug;
+use core::fmt::Debug;
 
-pub(crate) trait NodeTrait<State, Ctx>: Debug {
+pub(crate) trait NodeTrait<State, Ctx, S>: Debug + MaybeSync {
     fn constraints(
         &mut self,
-        available_area: Area,
+        available_area: Area<S>,
+        cells: bool,
         state: &mut State,
         ctx: &mut Ctx,
-    ) -> SizeConstraints;
+    ) -> SizeConstraints<S>;
+    #[allow(clippy::too_many_arguments)]
     fn layout(
         &mut self,
-        available_area: Area,
+        available_area: Area<S>,
         contextual_x_align: Option<XAlign>,
         contextual_y_align: Option<YAlign>,
+        visible: Area<S>,
+        direction: Direction,
+        cells: bool,
         state: &mut State,
         ctx: &mut Ctx,
     );
-    fn draw(&mut self, state: &mut State, ctx: &mut Ctx);
+    fn draw(&mut self, clip: Option<Area<S>>, state: &mut State, ctx: &mut Ctx);
+    fn computed(&self, area: Area<S>) -> Vec<ComputedNode<S>>;
+    fn animate(&mut self, animator: &mut Animator<S>);
+    fn map_areas(&mut self, f: &dyn Fn(Area<S>) -> Area<S>);
+    fn fade(&mut self, opacity: f32);
+    fn debug_nodes(&self, depth: usize, nodes: &mut Vec<DebugNode<S>>);
+    fn conflicts(
+        &self,
+        path: &mut Vec<usize>,
+        id: Option<u64>,
+        conflicts: &mut Vec<ConstraintConflict<S>>,
+    );
+    /// Returns an id that is used more than once within this node's own scope
+    fn duplicate_id(&self) -> Option<u64>;
 }
@@ -0,0 +1,147 @@
+use crate::traits::MaybeSync;
+use core::{
+    fmt::Debug,
+    ops::{Add, Div, Mul, Sub},
+};
+
+/// The number type layouts are calculated in, `f32` by default.
+///
+/// Implemented for `f32` & `f64`. Fixed-point types can implement it too, converting from `f64`
+/// for constants like `0.5` & for counts of elements.
+///
+/// The functions in [`crate::nodes`] build nodes in `f32`, so the closures passed to them keep their inferred types.
+/// Nodes in any other scalar are built with the associated functions of [`crate::NodeWith`],
+/// which are named like the functions in [`crate::nodes`] & pass the context to their closures like the `_with` functions,
+/// & laid out by a [`crate::Layout`] in the same scalar.
+///
+/// ```rust
+/// use backer::*;
+/// use backer::models::*;
+///
+/// // At this distance from the origin, f32 can't represent the half
+/// type Node64 = NodeWith<(), (), f64>;
+/// let computed = Layout::new(|_: &mut ()| {
+///     Node64::row(vec![Node64::space().id("left"), Node64::space()])
+/// })
+/// .compute(Area { x: 1e9, y: 0., width: 101., height: 100. }, &mut ());
+/// assert_eq!(computed.area("left").unwrap().width, 50.5);
+/// ```
+pub trait Scalar:
+    Copy
+    + Debug
+    + Default
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + MaybeSync
+    + 'static
+{
+    /// Zero
+    const ZERO: Self;
+    /// One
+    const ONE: Self;
+    /// Converts an `f64`, which may lose precision
+    fn from_f64(value: f64) -> Self;
+    /// Converts to an `f64`, which may lose precision
+    fn to_f64(self) -> f64;
+    /// The bits of the value, which differ for values that differ,
+    /// so retained layouts can tell whether a node changed (see [`crate::Layout::retained`])
+    fn to_bits(self) -> u64;
+    /// The largest whole number less than or equal to `self`
+    fn floor(self) -> Self;
+    /// The nearest whole number, rounding halves away from zero
+    fn round(self) -> Self;
+    /// Whether the value isn't a number, which is never the case for fixed-point types
+    fn is_nan(self) -> bool {
+        false
+    }
+    /// Whether the value is neither infinite nor not a number, which is always the case for fixed-point types
+    fn is_finite(self) -> bool {
+        true
+    }
+    /// The smaller of `self` & `other`
+    fn min(self, other: Self) -> Self {
+        if other < self {
+            other
+        } else {
+            self
+        }
+    }
+    /// The larger of `self` & `other`
+    fn max(self, other: Self) -> Self {
+        if other > self {
+            other
+        } else {
+            self
+        }
+    }
+    /// The distance of `self` from zero
+    fn abs(self) -> Self {
+        if self < Self::ZERO {
+            Self::ZERO - self
+        } else {
+            self
+        }
+    }
+    /// `self` limited to between `lower` & `upper`
+    fn clamp(self, lower: Self, upper: Self) -> Self {
+        self.max(lower).min(upper)
+    }
+}
+
+macro_rules! impl_float_scalar {
+    ($float:ty) => {
+        impl Scalar for $float {
+            const ZERO: Self = 0.;
+            const ONE: Self = 1.;
+            fn from_f64(value: f64) -> Self {
+                value as $float
+            }
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+            fn to_bits(self) -> u64 {
+                <$float>::to_bits(self) as u64
+            }
+            #[cfg(feature = "std")]
+            fn floor(self) -> Self {
+                <$float>::floor(self)
+            }
+            #[cfg(not(feature = "std"))]
+            fn floor(self) -> Self {
+                crate::math::floor(self as f64) as $float
+            }
+            #[cfg(feature = "std")]
+            fn round(self) -> Self {
+                <$float>::round(self)
+            }
+            #[cfg(not(feature = "std"))]
+            fn round(self) -> Self {
+                crate::math::round(self as f64) as $float
+            }
+            fn abs(self) -> Self {
+                <$float>::abs(self)
+            }
+            fn is_nan(self) -> bool {
+                <$float>::is_nan(self)
+            }
+            fn is_finite(self) -> bool {
+                <$float>::is_finite(self)
+            }
+            fn min(self, other: Self) -> Self {
+                <$float>::min(self, other)
+            }
+            fn max(self, other: Self) -> Self {
+                <$float>::max(self, other)
+            }
+            fn clamp(self, lower: Self, upper: Self) -> Self {
+                <$float>::clamp(self, lower, upper)
+            }
+        }
+    };
+}
+
+impl_float_scalar!(f32);
+impl_float_scalar!(f64);
@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use core::marker::PhantomData;
 
 /// Implement `Scopable` to enable usage with [`Node::scope`]
 ///
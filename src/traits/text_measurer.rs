@@ -0,0 +1,56 @@
+use crate::models::Monospace;
+
+/// Implement `TextMeasurer` for your `Ctx` to size [`crate::nodes::text`] nodes
+///
+/// ```rust
+/// use backer::traits::*;
+/// use backer::nodes::*;
+/// use backer::models::*;
+/// use backer::Layout;
+///
+/// // Usually this would call into your UI library's text layout
+/// struct MyCtx;
+/// impl TextMeasurer for MyCtx {
+///     type Style = f32;
+///     fn measure_text(&mut self, text: &str, size: &f32, max_width: Option<f32>) -> (f32, f32) {
+///         Monospace::new(size * 0.5, *size).measure_text(text, &(), max_width)
+///     }
+/// }
+///
+/// let layout = Layout::new_with(|_: &mut (), _: &mut MyCtx| {
+///     text("Hello world", 20., |area, _, _| {
+///         assert_eq!(area.height, 40.);
+///     })
+///     .width(60.)
+/// });
+/// layout.draw_with(Area::new(0., 0., 100., 100.), &mut (), &mut MyCtx);
+/// ```
+pub trait TextMeasurer {
+    /// Styling that affects the size of text, like a font & font size
+    type Style;
+    /// Returns the `(width, height)` of `text` in `style`, wrapped onto lines no wider than `max_width`.
+    ///
+    /// Text should only be broken onto multiple lines at line breaks when `max_width` is `None`.
+    fn measure_text(
+        &mut self,
+        text: &str,
+        style: &Self::Style,
+        max_width: Option<f32>,
+    ) -> (f32, f32);
+}
+
+impl TextMeasurer for Monospace {
+    type Style = ();
+    fn measure_text(&mut self, text: &str, _: &(), max_width: Option<f32>) -> (f32, f32) {
+        let lines = self.wrap(text, max_width);
+        let columns = lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        (
+            columns as f32 * self.char_width,
+            lines.len() as f32 * self.line_height,
+        )
+    }
+}
@@ -0,0 +1,367 @@
+use crate::prelude::*;
+use crate::{
+    animation::{progress, transformed},
+    drawable::Drawable,
+    layout::NodeValue,
+    models::{Area, Padding, Size, Transition},
+    node_cache::NodeCache,
+    retained::ChildKey,
+    traits::{NodeTrait, Scalar},
+};
+use alloc::collections::{BTreeMap, BTreeSet};
+use core::time::Duration;
+
+/// The enter & exit transitions of identified nodes, kept by a [`crate::Layout`] between frames
+pub(crate) struct Transitions<State, Ctx, S> {
+    /// Whether a frame has been laid out, since nodes in the first frame don't transition in
+    started: bool,
+    present: BTreeMap<u64, Presence<State, Ctx, S>>,
+    leaving: BTreeMap<u64, Leaving<State, Ctx, S>>,
+}
+
+struct Presence<State, Ctx, S> {
+    entered: Option<Duration>,
+    transition: Transition,
+    ghost: Option<Ghost<State, Ctx, S>>,
+}
+
+struct Leaving<State, Ctx, S> {
+    start: Duration,
+    ghost: Ghost<State, Ctx, S>,
+}
+
+/// Where a node was in its parent sequence, & what it drew, so it can be kept in place while it leaves
+struct Ghost<State, Ctx, S> {
+    /// The path from the root to the parent sequence
+    parent: Vec<ChildKey>,
+    index: usize,
+    /// The sibling before the node
+    after: Option<ChildKey>,
+    /// The area the parent sequence allocated to the node
+    slot: Area<S>,
+    /// The area the node occupied, relative to `slot`
+    area: Area<S>,
+    transition: Transition,
+    drawables: Vec<Drawable<State, Ctx, S>>,
+}
+
+/// Where a node is in its parent sequence
+#[derive(Clone)]
+struct Slot<S> {
+    parent: Vec<ChildKey>,
+    index: usize,
+    after: Option<ChildKey>,
+    area: Area<S>,
+}
+
+impl<State, Ctx, S: Scalar> Transitions<State, Ctx, S> {
+    pub(crate) fn new() -> Self {
+        Self {
+            started: false,
+            present: BTreeMap::new(),
+            leaving: BTreeMap::new(),
+        }
+    }
+
+    pub(crate) fn is_transitioning(&self, time: Duration) -> bool {
+        !self.leaving.is_empty()
+            || self.present.values().any(|presence| {
+                presence.entered.is_some_and(|entered| {
+                    progress(entered, presence.transition.duration, time) < 1.
+                })
+            })
+    }
+
+    /// Inserts every node that's leaving at `time` back into its parent sequence,
+    /// including nodes removed from the layout since the previous frame.
+    ///
+    /// Nothing is recorded until [`Transitions::apply`], so a frame that's rejected doesn't start exit transitions.
+    /// Returns the ids of the transitioned nodes in the layout, which are passed to `apply`.
+    pub(crate) fn insert_leaving(
+        &self,
+        root: &mut NodeCache<State, Ctx, S>,
+        time: Duration,
+    ) -> BTreeSet<u64> {
+        let mut ids = BTreeSet::new();
+        collect_ids(root, &mut ids);
+        let leaving = self.leaving_at(&ids, time);
+        if !leaving.is_empty() {
+            insert(&leaving, root, &mut Vec::new());
+        }
+        ids
+    }
+
+    /// The ghosts of nodes that are leaving at `time`, where `ids` are the ids of the transitioned nodes in the layout
+    fn leaving_at(&self, ids: &BTreeSet<u64>, time: Duration) -> Vec<(u64, &Ghost<State, Ctx, S>)> {
+        let removed = self
+            .present
+            .iter()
+            .filter_map(|(id, presence)| Some((*id, time, presence.ghost.as_ref()?)));
+        self.leaving
+            .iter()
+            .map(|(id, leaving)| (*id, leaving.start, &leaving.ghost))
+            .chain(removed)
+            // Nodes that are added back stop leaving & enter again
+            .filter(|(id, start, ghost)| {
+                !ids.contains(id) && progress(*start, ghost.transition.duration, time) < 1.
+            })
+            .map(|(id, _, ghost)| (id, ghost))
+            .collect()
+    }
+
+    /// Applies enter & exit transitions to the laid out tree, & records what transitioned nodes drew
+    /// so they can be kept in place when they're removed.
+    ///
+    /// `ids` are the ids returned by [`Transitions::insert_leaving`] for this frame.
+    pub(crate) fn apply(
+        &mut self,
+        root: &mut NodeCache<State, Ctx, S>,
+        ids: &BTreeSet<u64>,
+        time: Duration,
+    ) {
+        // Starts the exit transitions of the removed nodes `insert_leaving` inserted
+        let removed: Vec<u64> = self
+            .present
+            .keys()
+            .filter(|id| !ids.contains(id))
+            .copied()
+            .collect();
+        for id in removed {
+            if let Some(Presence {
+                ghost: Some(ghost), ..
+            }) = self.present.remove(&id)
+            {
+                self.leaving.insert(id, Leaving { start: time, ghost });
+            }
+        }
+        self.leaving.retain(|id, leaving| {
+            !ids.contains(id)
+                && progress(leaving.start, leaving.ghost.transition.duration, time) < 1.
+        });
+        let mut seen = BTreeSet::new();
+        self.visit(root, &mut Vec::new(), None, time, &mut seen);
+        self.present.retain(|id, _| seen.contains(id));
+        self.started = true;
+    }
+
+    fn visit(
+        &mut self,
+        node: &mut NodeCache<State, Ctx, S>,
+        path: &mut Vec<ChildKey>,
+        slot: Option<Slot<S>>,
+        time: Duration,
+        seen: &mut BTreeSet<u64>,
+    ) {
+        if let NodeValue::Transition {
+            id: Some(id),
+            transition,
+            element,
+        } = &mut node.kind
+        {
+            let (id, transition) = (*id, *transition);
+            if let Some(leaving) = self.leaving.get(&id) {
+                if let Some(slot) = element.occupied_area() {
+                    let area = Area {
+                        x: slot.x + leaving.ghost.area.x,
+                        y: slot.y + leaving.ghost.area.y,
+                        ..leaving.ghost.area
+                    };
+                    let leaving = progress(leaving.start, transition.duration, time);
+                    present(
+                        element,
+                        area,
+                        transition,
+                        1. - transition.easing.ease(leaving),
+                    );
+                }
+                return;
+            }
+            seen.insert(id);
+            let target = element.occupied_area();
+            let ghost = slot.clone().zip(target).map(|(slot, area)| Ghost {
+                parent: slot.parent,
+                index: slot.index,
+                after: slot.after,
+                slot: slot.area,
+                area: Area {
+                    x: area.x - slot.area.x,
+                    y: area.y - slot.area.y,
+                    ..area
+                },
+                transition,
+                drawables: drawables(element),
+            });
+            let started = self.started;
+            let presence = self.present.entry(id).or_insert(Presence {
+                entered: started.then_some(time),
+                transition,
+                ghost: None,
+            });
+            presence.transition = transition;
+            presence.ghost = ghost;
+            let entered = presence.entered;
+            path.push(element.kind.key(0));
+            self.visit(element, path, slot, time, seen);
+            path.pop();
+            if let (Some(entered), Some(target)) = (entered, target) {
+                let entering = progress(entered, transition.duration, time);
+                if entering < 1. {
+                    present(
+                        element,
+                        target,
+                        transition,
+                        transition.easing.ease(entering),
+                    );
+                }
+            }
+            return;
+        }
+        let sequence = matches!(
+            node.kind,
+            NodeValue::Row { .. } | NodeValue::Column { .. } | NodeValue::Stack { .. }
+        );
+        let mut previous = None;
+        for (i, child) in node.kind.children_mut().into_iter().enumerate() {
+            let key = child.kind.key(i);
+            let child_slot = if sequence {
+                child.layout_area.map(|area| Slot {
+                    parent: path.clone(),
+                    index: i,
+                    after: previous,
+                    area,
+                })
+            } else {
+                slot.clone()
+            };
+            previous = Some(key);
+            path.push(key);
+            self.visit(child, path, child_slot, time, seen);
+            path.pop();
+        }
+    }
+}
+
+impl<State, Ctx, S: Scalar> Ghost<State, Ctx, S> {
+    /// A node that redraws what a leaving node drew, sized to fill the same slot in its parent sequence
+    fn node(&self, id: u64) -> NodeValue<State, Ctx, S> {
+        let slot = self.slot;
+        let drawables = self
+            .drawables
+            .iter()
+            .map(|drawable| {
+                NodeCache::new(NodeValue::Padding {
+                    amounts: Padding {
+                        leading: drawable.area.x - slot.x,
+                        trailing: (slot.x + slot.width) - (drawable.area.x + drawable.area.width),
+                        top: drawable.area.y - slot.y,
+                        bottom: (slot.y + slot.height) - (drawable.area.y + drawable.area.height),
+                    },
+                    element: Box::new(NodeCache::new(NodeValue::Draw(drawable.clone()))),
+                })
+            })
+            .collect();
+        NodeValue::Transition {
+            id: Some(id),
+            transition: self.transition,
+            element: Box::new(NodeCache::new(NodeValue::Explicit {
+                options: Size {
+                    width_min: Some(slot.width),
+                    width_max: Some(slot.width),
+                    height_min: Some(slot.height),
+                    height_max: Some(slot.height),
+                    ..Default::default()
+                },
+                element: Box::new(NodeCache::new(NodeValue::Stack {
+                    elements: drawables,
+                    x_align: None,
+                    y_align: None,
+                })),
+            })),
+        }
+    }
+}
+
+/// Moves, scales & fades `element`, where `presence` is 0 when it's fully transitioned out & 1 when it's fully in
+fn present<State, Ctx, S: Scalar>(
+    element: &mut NodeCache<State, Ctx, S>,
+    target: Area<S>,
+    transition: Transition,
+    presence: f32,
+) {
+    let absence = 1. - presence;
+    let scale = S::from_f64((transition.scale + (1. - transition.scale) * presence) as f64);
+    let width = target.width * scale;
+    let height = target.height * scale;
+    let half = S::from_f64(0.5);
+    let area = Area {
+        x: target.x
+            + (target.width - width) * half
+            + S::from_f64((transition.offset_x * absence) as f64),
+        y: target.y
+            + (target.height - height) * half
+            + S::from_f64((transition.offset_y * absence) as f64),
+        width,
+        height,
+    };
+    element.map_areas(&|child| transformed(child, target, area));
+    element.fade(transition.opacity + (1. - transition.opacity) * presence);
+}
+
+/// Inserts the ghosts of `leaving` nodes into their parent sequences in `node` & its descendants
+fn insert<State, Ctx, S: Scalar>(
+    leaving: &[(u64, &Ghost<State, Ctx, S>)],
+    node: &mut NodeCache<State, Ctx, S>,
+    path: &mut Vec<ChildKey>,
+) {
+    if let NodeValue::Row { elements, .. }
+    | NodeValue::Column { elements, .. }
+    | NodeValue::Stack { elements, .. } = &mut node.kind
+    {
+        let mut siblings: Vec<&(u64, &Ghost<State, Ctx, S>)> = leaving
+            .iter()
+            .filter(|(_, ghost)| ghost.parent == *path)
+            .collect();
+        siblings.sort_by_key(|(_, ghost)| ghost.index);
+        for (id, ghost) in siblings {
+            let index = match ghost.after {
+                Some(after) => elements
+                    .iter()
+                    .enumerate()
+                    .position(|(i, element)| element.kind.key(i) == after)
+                    .map(|i| i + 1),
+                None => Some(0),
+            }
+            .unwrap_or(ghost.index)
+            .min(elements.len());
+            elements.insert(index, NodeCache::new(ghost.node(*id)));
+        }
+    }
+    for (i, child) in node.kind.children_mut().into_iter().enumerate() {
+        path.push(child.kind.key(i));
+        insert(leaving, child, path);
+        path.pop();
+    }
+}
+
+fn collect_ids<State, Ctx, S: Scalar>(node: &NodeCache<State, Ctx, S>, ids: &mut BTreeSet<u64>) {
+    if let NodeValue::Transition { id: Some(id), .. } = &node.kind {
+        ids.insert(*id);
+    }
+    node.kind
+        .children()
+        .into_iter()
+        .for_each(|child| collect_ids(child, ids));
+}
+
+fn drawables<State, Ctx, S: Scalar>(
+    node: &NodeCache<State, Ctx, S>,
+) -> Vec<Drawable<State, Ctx, S>> {
+    if let NodeValue::Draw(drawable) = &node.kind {
+        return vec![drawable.clone()];
+    }
+    node.kind
+        .children()
+        .into_iter()
+        .flat_map(|child| drawables(child))
+        .collect()
+}